@@ -0,0 +1,410 @@
+extern crate gcc;
+extern crate pkg_config;
+
+use std::env;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/* The hand-written declarations under test, relative to this crate. */
+const LL_FILES: &[&str] = &["../src/ll.rs", "../src/panel/ll.rs", "../src/menu/ll.rs", "../src/form/ll.rs"];
+
+fn main() {
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("cannot get OUT_DIR"));
+    for file in LL_FILES {
+        println!("cargo:rerun-if-changed={}", file);
+    }
+    for file in &["../src/constants.rs", "../src/attr.rs", "../src/ncurses.rs"] {
+        println!("cargo:rerun-if-changed={}", file);
+    }
+
+    let mut functions = Vec::new();
+    for file in LL_FILES {
+        functions.extend(parse_functions(&read(file)));
+    }
+    let values = rust_values();
+
+    let mut c = String::from("
+#include <locale.h>
+#include <stddef.h>
+#include <stdio.h>
+#include <wchar.h>
+
+#include <ncurses.h>
+#include <term.h>
+#include <panel.h>
+#include <menu.h>
+#include <form.h>
+
+struct abi_function { const char *name; const char *signature; int compatible; };
+struct abi_value { const char *name; int defined; long long value; };
+
+static const struct abi_function functions[] = {
+");
+    for f in &functions {
+        let checks = f.c_signatures().iter()
+                      .map(|sig| format!("__builtin_types_compatible_p(__typeof__(&{}), {})", f.link_name, sig))
+                      .collect::<Vec<_>>().join(" || ");
+        c.push_str(&format!("    {{ \"{}\", \"{}\", {} }},\n", f.name, f.c_signature(), checks));
+    }
+    c.push_str("};\n\nstatic const struct abi_value values[] = {\n");
+    for (name, c_expr, _) in &values {
+        match c_expr {
+            CValue::Macro(m) => c.push_str(&format!("#ifdef {}\n    {{ \"{}\", 1, (long long)({}) }},\n#else\n    {{ \"{}\", 0, 0 }},\n#endif\n",
+                                                        m, name, m, name)),
+            CValue::Expr(e) => c.push_str(&format!("    {{ \"{}\", 1, (long long)({}) }},\n", name, e)),
+        }
+    }
+    c.push_str("
+};
+
+const struct abi_function *ncurses_abi_functions(size_t *len)
+{
+    *len = sizeof functions / sizeof *functions;
+    return functions;
+}
+
+const struct abi_value *ncurses_abi_values(size_t *len)
+{
+    *len = sizeof values / sizeof *values;
+    return values;
+}
+");
+    let c_path = out_dir.join("abi.c");
+    write(&c_path, &c);
+
+    let mut rs = String::from("/* The Rust side of each value in abi.c. */\npub fn rust_values() -> Vec<(&'static str, i64)>\n{\n  vec![\n");
+    for (name, _, rust_expr) in &values {
+        rs.push_str(&format!("    (\"{}\", {} as i64),\n", name, rust_expr));
+    }
+    rs.push_str("  ]\n}\n");
+    write(&out_dir.join("abi.rs"), &rs);
+
+    let mut cfg = gcc::Config::new();
+    cfg.file(&c_path).define("NCURSES_WIDECHAR", Some("1"))
+       /* The deprecated functions are declared all the same. */
+       .flag("-Wno-deprecated-declarations");
+    if let Ok(lib) = pkg_config::Config::new().cargo_metadata(false).probe("ncursesw") {
+        for path in lib.include_paths {
+            cfg.include(path);
+        }
+    }
+    cfg.compile("libncurses_abi.a");
+}
+
+fn read(path: &str) -> String {
+    let mut s = String::new();
+    File::open(path).and_then(|mut f| f.read_to_string(&mut s))
+                    .expect(&format!("cannot read {}", path));
+    s
+}
+
+fn write(path: &Path, contents: &str) {
+    let mut fp = File::create(path).expect(&format!("cannot create {}", path.display()));
+    fp.write_all(contents.as_bytes()).expect(&format!("cannot write into {}", path.display()));
+}
+
+/* Drops // and /* */ comments. */
+fn strip_comments(src: &str) -> String {
+    let mut out = String::new();
+    let mut rest = src;
+    loop {
+        let line = rest.find("//");
+        let block = rest.find("/*");
+        match (line, block) {
+            (Some(l), b) if b.map_or(true, |b| l < b) => {
+                out.push_str(&rest[..l]);
+                rest = &rest[l..];
+                rest = &rest[rest.find('\n').unwrap_or(rest.len())..];
+            }
+            (_, Some(b)) => {
+                out.push_str(&rest[..b]);
+                rest = &rest[b..];
+                rest = &rest[rest.find("*/").map_or(rest.len(), |e| e + 2)..];
+            }
+            _ => {
+                out.push_str(rest);
+                return out;
+            }
+        }
+    }
+}
+
+struct Function {
+    name: String,
+    link_name: String,
+    params: Vec<String>,
+    ret: Option<String>,
+}
+
+impl Function {
+    /* The declaration as a C function pointer type. */
+    fn c_signature(&self) -> String {
+        fn_pointer(&self.params, self.ret.as_deref())
+    }
+
+    /* Every C type the declaration may match. The Rust handle types can't
+       say whether C takes a `const WINDOW *` or a `WINDOW *`, so both are
+       accepted for handle parameters. */
+    fn c_signatures(&self) -> Vec<String> {
+        let mut lists: Vec<Vec<String>> = vec![Vec::new()];
+        for param in &self.params {
+            let spellings = if HANDLES.contains(&param.as_str()) {
+                vec![param.clone(), format!("*const {}", param)]
+            } else {
+                vec![param.clone()]
+            };
+            lists = lists.iter().flat_map(|list| spellings.iter().map(move |s| {
+                let mut list = list.clone();
+                list.push(s.clone());
+                list
+            })).collect();
+        }
+        lists.iter().map(|params| fn_pointer(params, self.ret.as_deref())).collect()
+    }
+}
+
+/* The opaque pointer types, spelled `X *` in C. */
+const HANDLES: &[&str] = &["WINDOW", "SCREEN", "TERMINAL", "PANEL", "MENU", "ITEM", "FORM", "FIELD", "FIELDTYPE"];
+
+/* The `pub fn` items of every extern block in `src`. */
+fn parse_functions(src: &str) -> Vec<Function> {
+    let src = strip_comments(src);
+    let mut functions = Vec::new();
+    let mut rest = src.as_str();
+    while let Some(start) = find_extern_block(rest) {
+        let body = &rest[start..];
+        let end = body.find('}').expect("unterminated extern block");
+        for item in body[..end].split(';') {
+            let item = item.trim();
+            if item.is_empty() {
+                continue;
+            }
+            functions.push(parse_function(item));
+        }
+        rest = &body[end..];
+    }
+    functions
+}
+
+/* The offset just past the `{` of the next extern block. */
+fn find_extern_block(src: &str) -> Option<usize> {
+    let mut from = 0;
+    while let Some(at) = src[from..].find("extern") {
+        let after = &src[from + at + "extern".len()..];
+        let trimmed = after.trim_start();
+        let trimmed = trimmed.strip_prefix("\"C\"").unwrap_or(trimmed).trim_start();
+        if trimmed.starts_with('{') {
+            return Some(src.len() - trimmed.len() + 1);
+        }
+        from += at + "extern".len();
+    }
+    None
+}
+
+fn parse_function(item: &str) -> Function {
+    let mut link_name = None;
+    let mut decl = item;
+    while decl.starts_with("#[") {
+        let end = decl.find(']').expect("unterminated attribute");
+        let attr = &decl[2..end];
+        if attr.starts_with("link_name") {
+            link_name = attr.split('"').nth(1).map(|s| s.to_string());
+        }
+        decl = decl[end + 1..].trim_start();
+    }
+    let decl = decl.strip_prefix("pub fn").expect(&format!("not a function: {}", item)).trim();
+    let open = decl.find('(').expect("no parameter list");
+    let close = matching_paren(decl, open);
+    let name = decl[..open].trim().to_string();
+    let params = split_top_level(&decl[open + 1..close]).into_iter()
+                 .map(|p| match p.find(':') {
+                     Some(colon) if !p.starts_with("...") => p[colon + 1..].trim().to_string(),
+                     _ => p,
+                 })
+                 .collect();
+    let ret = decl[close + 1..].trim().strip_prefix("->").map(|r| r.trim().to_string());
+    Function { link_name: link_name.unwrap_or_else(|| name.clone()), name, params, ret }
+}
+
+fn matching_paren(s: &str, open: usize) -> usize {
+    let mut depth = 0;
+    for (i, ch) in s[open..].char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return open + i;
+                }
+            }
+            _ => {}
+        }
+    }
+    panic!("unbalanced parentheses in {}", s)
+}
+
+/* Splits a parameter list on the commas outside any nesting. */
+fn split_top_level(list: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut current = String::new();
+    for ch in list.chars() {
+        match ch {
+            '(' | '<' => depth += 1,
+            ')' | '>' if !current.ends_with('-') => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(ch);
+    }
+    if !current.trim().is_empty() {
+        parts.push(current.trim().to_string());
+    }
+    parts
+}
+
+fn fn_pointer(params: &[String], ret: Option<&str>) -> String {
+    let params = if params.is_empty() {
+        "void".to_string()
+    } else {
+        params.iter().map(|p| if p == "..." { p.clone() } else { c_type(p) })
+              .collect::<Vec<_>>().join(", ")
+    };
+    /* libc's c_void is a one-byte enum, so returning it by value is not
+       returning C void. */
+    let ret = match ret {
+        None => "void".to_string(),
+        Some("c_void") => "unsigned char".to_string(),
+        Some(ret) => c_type(ret),
+    };
+    format!("{} (*)({})", ret, params)
+}
+
+/* The C spelling of a Rust type from the ll modules. Rust aliases for
+   pointers are expanded as Rust defines them; the opaque handles and the
+   typedefs whose sizes lib.rs checks keep their C names. */
+fn c_type(t: &str) -> String {
+    let t = t.trim();
+    if let Some(inner) = t.strip_prefix("*const ") {
+        /* In the handle spellings above, the const goes on the WINDOW. */
+        if HANDLES.contains(&inner) {
+            return format!("{} const *", inner);
+        }
+        return format!("{} const *", c_type(inner));
+    }
+    if let Some(inner) = t.strip_prefix("*mut ") {
+        return format!("{} *", c_type(inner));
+    }
+    if let Some(f) = t.strip_prefix("Option<").and_then(|f| f.strip_suffix('>')) {
+        let f = f.trim().strip_prefix("extern \"C\" fn").expect(&format!("unsupported type {}", t));
+        let close = matching_paren(f, 0);
+        let params = split_top_level(&f[1..close]);
+        let ret = f[close + 1..].trim().strip_prefix("->").map(|r| r.trim());
+        /* Wrapped so it composes like a plain type name, even as the
+           return type of another function pointer. */
+        return format!("__typeof__({})", fn_pointer(&params, ret));
+    }
+    let alias = match t {
+        "char_p" => Some("*const c_char"),
+        "chtype_p" => Some("*const chtype"),
+        "void_p" => Some("*const c_void"),
+        "attr_t_p" => Some("*mut attr_t"),
+        "short_p" => Some("*mut c_short"),
+        "cchar_t_p" => Some("*const cchar_t"),
+        "wchar_t_p" => Some("*const wchar_t"),
+        "FILE_p" => Some("*mut FILE"),
+        "NCURSES_ATTR_T" => Some("attr_t"),
+        "winttype" => Some("c_uint"),
+        "FieldOptions" => Some("c_int"),
+        "HOOK" => Some("Option<extern \"C\" fn(MENU)>"),
+        "NCURSES_WINDOW_CB" => Some("Option<extern \"C\" fn(WINDOW, *mut c_void) -> c_int>"),
+        "NCURSES_SCREEN_CB" => Some("Option<extern \"C\" fn(SCREEN, *mut c_void) -> c_int>"),
+        _ => None,
+    };
+    if let Some(alias) = alias {
+        return c_type(alias);
+    }
+    match t {
+        "c_int" => "int",
+        "c_uint" => "unsigned int",
+        "c_short" => "short",
+        "c_char" => "char",
+        "c_ulong" => "unsigned long",
+        "c_void" => "void",
+        "c_bool" => "bool",
+        "chtype" | "attr_t" | "mmask_t" | "MEVENT" | "cchar_t" | "wchar_t" | "FILE" | "va_list" => t,
+        _ if HANDLES.contains(&t) => return format!("{} *", t),
+        _ => panic!("no C spelling for the Rust type {}", t),
+    }.to_string()
+}
+
+/* The declaration after `pub const`, skipping const functions. */
+fn const_decl(line: &str) -> Option<&str> {
+    line.strip_prefix("pub const ").filter(|decl| !decl.starts_with("fn "))
+}
+
+enum CValue {
+    /* A macro, which may be missing from older headers. */
+    Macro(String),
+    Expr(String),
+}
+
+/* The constants written by hand in the crate and the type layouts, as
+   (name, C value, Rust expression). The constants the crate's build.rs
+   generates come from the headers already. */
+fn rust_values() -> Vec<(String, CValue, String)> {
+    let mut values = Vec::new();
+    for line in read("../src/constants.rs").lines() {
+        if let Some(name) = const_decl(line).and_then(|l| l.split(':').next()) {
+            values.push((name.to_string(), CValue::Macro(name.to_string()),
+                         format!("::ncurses::constants::{}", name)));
+        }
+    }
+    for line in read("../src/ncurses.rs").lines() {
+        if let Some(name) = line.strip_prefix("pub fn A_").and_then(|l| l.strip_suffix("() -> attr_t")) {
+            let name = format!("A_{}", name);
+            values.push((name.clone(), CValue::Macro(name.clone()), format!("::ncurses::{}()", name)));
+        }
+    }
+    for line in read("../src/attr.rs").lines() {
+        let line = line.trim();
+        if let Some(decl) = const_decl(line) {
+            let mut parts = decl.splitn(2, ':');
+            let (name, ty) = (parts.next().unwrap_or(""), parts.next().unwrap_or("").trim());
+            /* ALL leaves out the color bits that A_ATTRIBUTES covers. */
+            let c_value = if name == "ALL" {
+                CValue::Expr("A_ATTRIBUTES & ~A_COLOR".to_string())
+            } else {
+                CValue::Macro(format!("A_{}", name))
+            };
+            let rust = if ty.starts_with("Attr") {
+                format!("::ncurses::attr::Attr::{}.bits()", name)
+            } else {
+                format!("::ncurses::attr::Attr::{}", name)
+            };
+            values.push((format!("Attr::{}", name), c_value, rust));
+        }
+    }
+
+    /* Layouts, with the Rust type standing for each C one. */
+    let types = [("chtype", "::ncurses::ll::chtype"), ("attr_t", "::ncurses::ll::attr_t"),
+                 ("mmask_t", "::ncurses::ll::mmask_t"), ("MEVENT", "::ncurses::ll::MEVENT"),
+                 ("cchar_t", "::ncurses::ll::cchar_t"), ("bool", "::ncurses::ll::c_bool"),
+                 ("wchar_t", "::libc::wchar_t"), ("wint_t", "::ncurses::ll::winttype")];
+    for &(c_ty, rust_ty) in &types {
+        values.push((format!("sizeof({})", c_ty), CValue::Expr(format!("sizeof({})", c_ty)),
+                     format!("::std::mem::size_of::<{}>()", rust_ty)));
+        values.push((format!("alignof({})", c_ty), CValue::Expr(format!("_Alignof({})", c_ty)),
+                     format!("::std::mem::align_of::<{}>()", rust_ty)));
+    }
+    for field in &["id", "x", "y", "z", "bstate"] {
+        values.push((format!("offsetof(MEVENT, {})", field), CValue::Expr(format!("offsetof(MEVENT, {})", field)),
+                     format!("::std::mem::offset_of!(::ncurses::ll::MEVENT, {})", field)));
+    }
+    values
+}
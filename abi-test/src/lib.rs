@@ -0,0 +1,91 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: lib.rs
+    Description:
+      Compares the hand-written declarations of
+      ncurses-rs with the installed C headers,
+      through a shim generated by build.rs.
+*/
+
+extern crate libc;
+extern crate ncurses;
+
+use std::ffi::CStr;
+use libc::{c_char, c_int, c_longlong, size_t};
+
+include!(concat!(env!("OUT_DIR"), "/abi.rs"));
+
+#[repr(C)]
+struct AbiFunction
+{
+  name: *const c_char,
+  signature: *const c_char,
+  compatible: c_int,
+}
+
+#[repr(C)]
+struct AbiValue
+{
+  name: *const c_char,
+  defined: c_int,
+  value: c_longlong,
+}
+
+extern "C"
+{
+  fn ncurses_abi_functions(len: *mut size_t) -> *const AbiFunction;
+  fn ncurses_abi_values(len: *mut size_t) -> *const AbiValue;
+}
+
+fn string(s: *const c_char) -> String
+{ unsafe { CStr::from_ptr(s).to_string_lossy().into_owned() } }
+
+
+/// Each function in the ll modules whose declaration doesn't match its
+/// C prototype, described with the Rust declaration spelled in C.
+pub fn function_mismatches() -> Vec<String>
+{
+  let mut len = 0;
+  let functions = unsafe { std::slice::from_raw_parts(ncurses_abi_functions(&mut len), len) };
+  functions.iter()
+           .filter(|f| f.compatible == 0)
+           .map(|f| format!("{}: declared as {}", string(f.name), string(f.signature)))
+           .collect()
+}
+
+
+/// Each constant or layout whose Rust value differs from the headers'.
+pub fn value_mismatches() -> Vec<String>
+{
+  let mut len = 0;
+  let values = unsafe { std::slice::from_raw_parts(ncurses_abi_values(&mut len), len) };
+  let rust = rust_values();
+  assert_eq!(values.len(), rust.len(), "abi.c and abi.rs disagree");
+
+  values.iter().zip(rust)
+        .filter_map(|(c, (name, value))|
+        {
+          if c.defined == 0
+          { Some(format!("{}: not in the headers", name)) }
+          else if c.value != value
+          { Some(format!("{}: {} in Rust, {} in C", name, value, c.value)) }
+          else
+          { None }
+        })
+        .collect()
+}
+
+
+/// How many functions and values the shim checks.
+pub fn counts() -> (usize, usize)
+{
+  let (mut functions, mut values) = (0, 0);
+  unsafe
+  {
+    ncurses_abi_functions(&mut functions);
+    ncurses_abi_values(&mut values);
+  }
+  (functions, values)
+}
@@ -0,0 +1,29 @@
+#![cfg(target_os="linux")]
+
+extern crate ncurses_abi_test;
+
+use ncurses_abi_test::{counts, function_mismatches, value_mismatches};
+
+#[test]
+fn every_declaration_is_checked()
+{
+  let (functions, values) = counts();
+  assert!(functions > 500, "only {} functions parsed", functions);
+  assert!(values > 50, "only {} values parsed", values);
+}
+
+
+#[test]
+fn functions_match_headers()
+{
+  let mismatches = function_mismatches();
+  assert!(mismatches.is_empty(), "{} mismatched functions:\n{}", mismatches.len(), mismatches.join("\n"));
+}
+
+
+#[test]
+fn constants_and_layouts_match_headers()
+{
+  let mismatches = value_mismatches();
+  assert!(mismatches.is_empty(), "{} mismatched values:\n{}", mismatches.len(), mismatches.join("\n"));
+}
@@ -2,22 +2,279 @@ extern crate gcc;
 extern crate pkg_config;
 
 use std::env;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/* Overrides for systems where pkg-config can't find ncurses, or finds the
+   wrong one. */
+const ENV_OVERRIDES: &[&str] = &[
+    "NCURSES_NO_PKG_CONFIG", "NCURSES_INCLUDE_DIR", "NCURSES_LIB_DIR", "NCURSES_SRC_DIR",
+];
+
 fn main() {
-    check_chtype_size();
+    for var in ENV_OVERRIDES {
+        println!("cargo:rerun-if-env-changed={}", var);
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let found = if feature("vendored") { build_vendored() } else { probe_ncurses() };
+    for path in &found.link_paths {
+        println!("cargo:rustc-link-search=native={}", path.display());
+    }
+    link_libraries(&found);
+
+    let include_paths = &found.include_paths;
+    check_chtype_size(include_paths);
+    gen_ll_abi(include_paths);
+    gen_constants(include_paths);
+    if feature("menu") {
+        gen_menu_constants(include_paths);
+    }
+    if feature("form") {
+        gen_form_constants(include_paths);
+    }
+}
+
+fn feature(name: &str) -> bool {
+    env::var_os(format!("CARGO_FEATURE_{}", name.to_uppercase())).is_some()
+}
+
+fn env_paths(var: &str) -> Vec<PathBuf> {
+    env::var_os(var).map(|v| env::split_paths(&v).collect()).unwrap_or_default()
+}
+
+/* Where the headers and libraries were found, and the libraries to link
+   in link order. */
+struct Ncurses {
+    include_paths: Vec<PathBuf>,
+    link_paths: Vec<PathBuf>,
+    libs: Vec<String>,
+}
+
+/* The name ncurses gives `base` in the configured flavour: "t" for the
+   reentrant build, then "w" for the wide one, as in "ncursestw". */
+fn lib_name(base: &str) -> String {
+    let mut name = base.to_string();
+    if feature("threaded") {
+        name.push('t');
+    }
+    if feature("wide") {
+        name.push('w');
+    }
+    name
+}
+
+/* `name` as the system has it installed: macOS ships a single
+   wide-capable library under the plain name. */
+fn system_lib_name(name: &str) -> String {
+    if feature("wide") && env::var("CARGO_CFG_TARGET_OS").ok().as_deref() == Some("macos") {
+        name.trim_end_matches('w').to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/* The libraries the enabled features need, add-ons before ncurses itself
+   so that static archives precede the ones they depend on. */
+fn linked_libs() -> Vec<String> {
+    let mut libs: Vec<String> = ["form", "menu", "panel"].iter().filter(|add_on| feature(add_on))
+                                                         .map(|add_on| lib_name(add_on))
+                                                         .collect();
+    libs.push(lib_name("ncurses"));
+    libs
+}
+
+fn probe_ncurses() -> Ncurses {
+    let mut found = Ncurses {
+        include_paths: env_paths("NCURSES_INCLUDE_DIR"),
+        link_paths: env_paths("NCURSES_LIB_DIR"),
+        libs: Vec::new(),
+    };
+    let use_pkg_config = env::var_os("NCURSES_NO_PKG_CONFIG").is_none();
+
+    for name in linked_libs().iter().map(|name| system_lib_name(name)) {
+        match if use_pkg_config { probe_pkg_config(&name) } else { None } {
+            /* pkg-config also names what the library needs, such as the
+               matching ncursesw and tinfo for panelw. */
+            Some(lib) => {
+                found.include_paths.extend(lib.include_paths);
+                found.link_paths.extend(lib.link_paths);
+                found.libs.extend(lib.libs);
+            }
+            None => found.libs.push(name),
+        }
+    }
+    found
 }
 
-fn check_chtype_size() {
+/* Looks `name` up in pkg-config, trying the package of the ncurses 5 ABI
+   ("ncursesw5") first. The link lines are left to `link_libraries`. */
+fn probe_pkg_config(name: &str) -> Option<pkg_config::Library> {
+    let abi5 = format!("{}5", name);
+    [abi5.as_str(), name].iter().filter_map(|package| {
+        pkg_config::Config::new().cargo_metadata(false)
+                                 .statik(feature("static"))
+                                 .probe(package).ok()
+    }).next()
+}
+
+/* Configures and builds the ncurses source tree named by NCURSES_SRC_DIR
+   into OUT_DIR, as static libraries only. */
+fn build_vendored() -> Ncurses {
+    let src = env::var_os("NCURSES_SRC_DIR")
+              .expect("the vendored feature needs NCURSES_SRC_DIR set to an ncurses source tree");
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("cannot get OUT_DIR"));
+    let build_dir = out_dir.join("ncurses-build");
+    let prefix = out_dir.join("ncurses");
+    fs::create_dir_all(&build_dir).expect(&format!("cannot create {}", build_dir.display()));
+
+    let compiler = gcc::Config::new().get_compiler();
+    let cflags = compiler.args().iter().map(|a| a.to_string_lossy().into_owned())
+                                .collect::<Vec<_>>().join(" ");
+
+    let mut configure = Command::new(Path::new(&src).join("configure"));
+    configure.current_dir(&build_dir)
+             .env("CC", compiler.path())
+             .env("CFLAGS", cflags)
+             .arg(format!("--prefix={}", prefix.display()))
+             .args(["--without-shared", "--with-normal", "--with-termlib",
+                     "--without-debug", "--without-ada", "--without-cxx-binding",
+                     "--without-progs", "--without-tests", "--without-manpages",
+                     "--disable-db-install", "--disable-pc-files",
+                     "--with-terminfo-dirs=/etc/terminfo:/lib/terminfo:/usr/share/terminfo",
+                     "--with-default-terminfo-dir=/usr/share/terminfo"]);
+    if feature("wide") {
+        configure.arg("--enable-widec");
+    }
+    if feature("threaded") {
+        configure.arg("--with-pthread").arg("--enable-reentrant");
+    }
+    let target = env::var("TARGET").expect("cannot get TARGET");
+    if env::var("HOST").ok().as_ref() != Some(&target) {
+        configure.arg(format!("--host={}", target));
+    }
+    run(&mut configure);
+
+    let mut make = Command::new(env::var_os("MAKE").unwrap_or_else(|| "make".into()));
+    make.current_dir(&build_dir).arg("install");
+    if let Ok(jobs) = env::var("NUM_JOBS") {
+        make.arg(format!("-j{}", jobs));
+    }
+    run(&mut make);
+
+    /* Without --enable-overwrite the headers land in a subdirectory. */
+    let include = prefix.join("include");
+    Ncurses {
+        include_paths: vec![include.join(lib_name("ncurses")), include],
+        link_paths: vec![prefix.join("lib")],
+        libs: linked_libs(),
+    }
+}
+
+fn run(cmd: &mut Command) {
+    let status = cmd.status().expect(&format!("cannot run {:?}", cmd));
+    assert!(status.success(), "{:?} failed with {}", cmd, status);
+}
+
+/* Emits the link lines for everything in `found.libs`, and for a separate
+   tinfo library, as left by a split build, when nothing named it. */
+fn link_libraries(found: &Ncurses) {
+    let statik = feature("static") || feature("vendored");
+    let kind = if statik { "static" } else { "dylib" };
+
+    /* A library named twice stays in its last place, after everything
+       that needs it. */
+    let mut libs: Vec<String> = Vec::new();
+    for lib in found.libs.iter().rev() {
+        if !libs.contains(lib) {
+            libs.push(lib.clone());
+        }
+    }
+    libs.reverse();
+    if !libs.iter().any(|lib| lib.starts_with("tinfo")) {
+        libs.extend(find_tinfo(found, statik));
+    }
+
+    for lib in libs {
+        /* System libraries pkg-config adds, like dl, are linked the
+           usual way. */
+        if ["ncurses", "tinfo", "panel", "menu", "form"].iter().any(|base| lib.starts_with(base)) {
+            println!("cargo:rustc-link-lib={}={}", kind, lib);
+        } else {
+            println!("cargo:rustc-link-lib={}", lib);
+        }
+    }
+}
+
+/* The terminfo library split out of ncurses, if there is one; it is named
+   like ncurses in some builds and plain "tinfo" in others. */
+fn find_tinfo(found: &Ncurses, statik: bool) -> Option<String> {
+    if env::var("CARGO_CFG_TARGET_OS").ok().as_deref() == Some("macos") {
+        return None;
+    }
+    let ext = if statik { "a" } else { "so" };
+    let compiler = gcc::Config::new().get_compiler();
+    let mut names = vec![lib_name("tinfo"), "tinfo".to_string()];
+    names.dedup();
+    names.into_iter().find(|name| {
+        let file = format!("lib{}.{}", name, ext);
+        if found.link_paths.iter().any(|dir| dir.join(&file).exists()) {
+            return true;
+        }
+        /* Otherwise ask the compiler, which answers with the bare name
+           when the file isn't on its search path. */
+        Command::new(compiler.path()).arg(format!("-print-file-name={}", file))
+                                     .output()
+                                     .is_ok_and(|out| {
+                                         let path = String::from_utf8_lossy(&out.stdout);
+                                         Path::new(path.trim()).is_absolute()
+                                     })
+    })
+}
+
+/* Compiles a C program against the installed headers and returns what it prints. */
+fn run_probe(name: &str, source: &str, include_paths: &[PathBuf]) -> String {
+    let out_dir = env::var("OUT_DIR").expect("cannot get OUT_DIR");
+    let src = Path::new(&out_dir).join(format!("{}.c", name));
+    let bin = Path::new(&out_dir).join(name);
+
+    let mut fp = File::create(&src).expect(&format!("cannot create {}", src.display()));
+    fp.write_all(source.as_bytes()).expect(&format!("cannot write into {}", src.display()));
+
+    let cfg = gcc::Config::new();
+    let compiler = cfg.get_compiler();
+
+    let mut cc = Command::new(compiler.path());
+    for path in include_paths {
+        cc.arg("-I").arg(path);
+    }
+    /* Expose cchar_t; the probes check for it before use. */
+    cc.arg("-DNCURSES_WIDECHAR=1");
+    let status = cc.arg(&src).arg("-o").arg(&bin)
+                   .status().expect("compilation failed");
+    assert!(status.success(),
+            "cannot compile {}; if <ncurses.h> is not on the default include path, \
+             set NCURSES_INCLUDE_DIR to its directory", src.display());
+    let output = Command::new(&bin).output()
+                 .expect(&format!("{} failed", bin.display()));
+    assert!(output.status.success(), "{} failed", bin.display());
+
+    fs::remove_file(&src).expect(&format!("cannot delete {}", src.display()));
+    fs::remove_file(&bin).expect(&format!("cannot delete {}", bin.display()));
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn write_out(name: &str, contents: &str) {
     let out_dir = env::var("OUT_DIR").expect("cannot get OUT_DIR");
-    let src = format!("{}", Path::new(&out_dir).join("chtype_size.c").display());
-    let bin = format!("{}", Path::new(&out_dir).join("chtype_size").display());
+    let path = Path::new(&out_dir).join(name);
+    let mut fp = File::create(&path).expect(&format!("cannot create {}", path.display()));
+    fp.write_all(contents.as_bytes()).expect(&format!("cannot write into {}", path.display()));
+}
 
-    let mut fp = File::create(&src).expect(&format!("cannot create {}", src));
-    fp.write_all(b"
+fn check_chtype_size(include_paths: &[PathBuf]) {
+    let features = run_probe("chtype_size", "
 #include <assert.h>
 #include <limits.h>
 #include <stdio.h>
@@ -32,26 +289,204 @@ int main(void)
         /* We only support 32-bit and 64-bit chtype. */
         assert(sizeof(chtype)*CHAR_BIT == 32 && \"unsupported size for chtype\");
     }
+#ifdef NCURSES_EXT_COLORS
+    puts(\"cargo:rustc-cfg=feature=\\\"ext_colors\\\"\");
+#endif
+#if NCURSES_VERSION_MAJOR > 6 || (NCURSES_VERSION_MAJOR == 6 && NCURSES_VERSION_MINOR >= 5)
+    puts(\"cargo:rustc-cfg=feature=\\\"mode_queries\\\"\");
+#endif
     return 0;
 }
-    ").expect(&format!("cannot write into {}", src));
+    ", include_paths);
+    print!("{}", features);
+}
 
-    let cfg = gcc::Config::new();
-    let compiler = cfg.get_compiler();
+/* The start of every generated-constants probe. */
+const PROBE_HEADER: &str = "
+#include <limits.h>
+#include <locale.h>
+#include <stddef.h>
+#include <stdio.h>
+
+#include <ncurses.h>
+
+#define INT(type, name, value) \
+    printf(\"pub const %s: %s = %lld;\\n\", name, type, (long long)(value))
+#define MASK(name, value) \
+    printf(\"pub const %s: mmask_t = 0x%llx;\\n\", name, (unsigned long long)(value))
+";
+
+/* printf calls emitting `names` as Rust constants of `ty`. A name missing
+   from the headers, such as one newer than the installed ncurses, comes
+   out as `fallback` when one is given and as a compile_error! naming it
+   otherwise. */
+fn emit_ints(c: &mut String, ty: &str, names: &[&str], fallback: Option<&str>) {
+    for name in names {
+        c.push_str(&format!("#ifdef {}\n    INT(\"{}\", \"{}\", {});\n#else\n", name, ty, name, name));
+        match fallback {
+            Some(value) => c.push_str(&format!("    INT(\"{}\", \"{}\", {});\n", ty, name, value)),
+            None => c.push_str(&format!(
+                "    printf(\"compile_error!(\\\"{} is not defined by the ncurses headers\\\");\\n\");\n",
+                name
+            )),
+        }
+        c.push_str("#endif\n");
+    }
+}
+
+fn gen_ll_abi(include_paths: &[PathBuf]) {
+    let source = format!("{}{}", PROBE_HEADER, "
+int main(void)
+{
+    printf(\"pub type mmask_t = u%d;\\n\", (int)(sizeof(mmask_t) * CHAR_BIT));
+    printf(\"const MEVENT_SIZE: usize = %d;\\n\", (int)sizeof(MEVENT));
+    printf(\"const MEVENT_OFFSETS: [usize; 5] = [%d, %d, %d, %d, %d];\\n\",
+           (int)offsetof(MEVENT, id), (int)offsetof(MEVENT, x), (int)offsetof(MEVENT, y),
+           (int)offsetof(MEVENT, z), (int)offsetof(MEVENT, bstate));
+#ifdef CCHARW_MAX
+    printf(\"pub const CCHARW_MAX: usize = %d;\\n\", CCHARW_MAX);
+    printf(\"const CCHAR_T_SIZE: usize = %d;\\n\", (int)sizeof(cchar_t));
+#else
+    printf(\"pub const CCHARW_MAX: usize = 5;\\n\");
+#endif
+    return 0;
+}
+");
+    write_out("ll_abi.rs", &run_probe("ll_abi", &source, include_paths));
+}
+
+const WINDOW_FLAGS: &[&str] = &[
+    "_SUBWIN", "_ENDLINE", "_FULLWIN", "_SCROLLWIN", "_ISPAD", "_HASMOVED", "_WRAPPED",
+    "_NOCHANGE", "_NEWINDEX",
+];
+
+const KEYS: &[&str] = &[
+    "KEY_CODE_YES", "KEY_MIN", "KEY_BREAK", "KEY_SRESET", "KEY_RESET", "KEY_DOWN", "KEY_UP",
+    "KEY_LEFT", "KEY_RIGHT", "KEY_HOME", "KEY_BACKSPACE", "KEY_F0", "KEY_DL", "KEY_IL",
+    "KEY_DC", "KEY_IC", "KEY_EIC", "KEY_CLEAR", "KEY_EOS", "KEY_EOL", "KEY_SF", "KEY_SR",
+    "KEY_NPAGE", "KEY_PPAGE", "KEY_STAB", "KEY_CTAB", "KEY_CATAB", "KEY_ENTER", "KEY_PRINT",
+    "KEY_LL", "KEY_A1", "KEY_A3", "KEY_B2", "KEY_C1", "KEY_C3", "KEY_BTAB", "KEY_BEG",
+    "KEY_CANCEL", "KEY_CLOSE", "KEY_COMMAND", "KEY_COPY", "KEY_CREATE", "KEY_END", "KEY_EXIT",
+    "KEY_FIND", "KEY_HELP", "KEY_MARK", "KEY_MESSAGE", "KEY_MOVE", "KEY_NEXT", "KEY_OPEN",
+    "KEY_OPTIONS", "KEY_PREVIOUS", "KEY_REDO", "KEY_REFERENCE", "KEY_REFRESH", "KEY_REPLACE",
+    "KEY_RESTART", "KEY_RESUME", "KEY_SAVE", "KEY_SBEG", "KEY_SCANCEL", "KEY_SCOMMAND",
+    "KEY_SCOPY", "KEY_SCREATE", "KEY_SDC", "KEY_SDL", "KEY_SELECT", "KEY_SEND", "KEY_SEOL",
+    "KEY_SEXIT", "KEY_SFIND", "KEY_SHELP", "KEY_SHOME", "KEY_SIC", "KEY_SLEFT", "KEY_SMESSAGE",
+    "KEY_SMOVE", "KEY_SNEXT", "KEY_SOPTIONS", "KEY_SPREVIOUS", "KEY_SPRINT", "KEY_SREDO",
+    "KEY_SREPLACE", "KEY_SRIGHT", "KEY_SRSUME", "KEY_SSAVE", "KEY_SSUSPEND", "KEY_SUNDO",
+    "KEY_SUSPEND", "KEY_UNDO", "KEY_MOUSE", "KEY_RESIZE", "KEY_MAX",
+];
+
+const MOUSE_EVENTS: &[&str] = &[
+    "NCURSES_BUTTON_RELEASED", "NCURSES_BUTTON_PRESSED", "NCURSES_BUTTON_CLICKED",
+    "NCURSES_DOUBLE_CLICKED", "NCURSES_TRIPLE_CLICKED", "NCURSES_RESERVED_EVENT",
+];
+
+const MOUSE_ACTIONS: &[&str] = &[
+    "RELEASED", "PRESSED", "CLICKED", "DOUBLE_CLICKED", "TRIPLE_CLICKED",
+];
 
-    Command::new(compiler.path()).arg(&src).arg("-o").arg(&bin)
-                                 .status().expect("compilation failed");
-    let features = Command::new(&bin).output()
-                   .expect(&format!("{} failed", bin));
-    print!("{}", String::from_utf8_lossy(&features.stdout));
+const MOUSE_FLAGS: &[&str] = &[
+    "BUTTON_CTRL", "BUTTON_SHIFT", "BUTTON_ALT", "REPORT_MOUSE_POSITION", "ALL_MOUSE_EVENTS",
+];
 
-    let ncurses_names = ["ncurses5", "ncurses"];
-    for ncurses_name in &ncurses_names {
-        if pkg_config::probe_library(ncurses_name).is_ok() {
-            break;
+const LOCALE_CATEGORIES: &[&str] = &[
+    "LC_ALL", "LC_COLLATE", "LC_CTYPE", "LC_MONETARY", "LC_NUMERIC", "LC_TIME", "LC_MESSAGES",
+];
+
+fn gen_constants(include_paths: &[PathBuf]) {
+    let mut c = String::from(PROBE_HEADER);
+    c.push_str("
+/* Only defined by libraries built with --enable-wgetch-events. */
+#ifndef KEY_EVENT
+#define KEY_EVENT 0633
+#endif
+
+int main(void)
+{
+    INT(\"u32\", \"NCURSES_ATTR_SHIFT\", NCURSES_ATTR_SHIFT);
+");
+    emit_ints(&mut c, "i32", WINDOW_FLAGS, None);
+    emit_ints(&mut c, "i32", KEYS, None);
+    c.push_str("    INT(\"i32\", \"KEY_EVENT\", KEY_EVENT);\n");
+    for n in 1..16 {
+        c.push_str(&format!("    INT(\"i32\", \"KEY_F{}\", KEY_F({}));\n", n, n));
+    }
+
+    c.push_str("    INT(\"i32\", \"NCURSES_MOUSE_VERSION\", NCURSES_MOUSE_VERSION);\n");
+    for name in MOUSE_EVENTS {
+        c.push_str(&format!("    MASK(\"{}\", {});\n", name, name));
+    }
+    /* Button 5 only exists in the version 2 mouse protocol. */
+    for button in 1..6 {
+        for action in MOUSE_ACTIONS {
+            let name = format!("BUTTON{}_{}", button, action);
+            c.push_str(&format!("#ifdef {0}\n    MASK(\"{0}\", {0});\n#else\n    MASK(\"{0}\", 0);\n#endif\n", name));
         }
     }
+    for name in MOUSE_FLAGS {
+        c.push_str(&format!("    MASK(\"{}\", {});\n", name, name));
+    }
+
+    emit_ints(&mut c, "c_int", LOCALE_CATEGORIES, None);
+    c.push_str("    return 0;\n}\n");
+
+    write_out("constants.rs", &run_probe("constants", &c, include_paths));
+}
+
+const MENU_OPTIONS: &[&str] = &[
+    "O_ONEVALUE", "O_SHOWDESC", "O_ROWMAJOR", "O_IGNORECASE", "O_SHOWMATCH", "O_NONCYCLIC",
+    "O_SELECTABLE",
+];
+
+const MENU_REQUESTS: &[&str] = &[
+    "REQ_LEFT_ITEM", "REQ_RIGHT_ITEM", "REQ_UP_ITEM", "REQ_DOWN_ITEM", "REQ_SCR_ULINE",
+    "REQ_SCR_DLINE", "REQ_SCR_DPAGE", "REQ_SCR_UPAGE", "REQ_FIRST_ITEM", "REQ_LAST_ITEM",
+    "REQ_NEXT_ITEM", "REQ_PREV_ITEM", "REQ_TOGGLE_ITEM", "REQ_CLEAR_PATTERN",
+    "REQ_BACK_PATTERN", "REQ_NEXT_MATCH", "REQ_PREV_MATCH", "MIN_MENU_COMMAND",
+    "MAX_MENU_COMMAND",
+];
+
+fn gen_menu_constants(include_paths: &[PathBuf]) {
+    let mut c = String::from(PROBE_HEADER);
+    c.push_str("#include <menu.h>\n\nint main(void)\n{\n");
+    emit_ints(&mut c, "i32", MENU_OPTIONS, None);
+    emit_ints(&mut c, "i32", MENU_REQUESTS, None);
+    c.push_str("    return 0;\n}\n");
+
+    write_out("menu_constants.rs", &run_probe("menu_constants", &c, include_paths));
+}
+
+const FORM_REQUESTS: &[&str] = &[
+    "REQ_NEXT_PAGE", "REQ_PREV_PAGE", "REQ_FIRST_PAGE", "REQ_LAST_PAGE", "REQ_NEXT_FIELD",
+    "REQ_PREV_FIELD", "REQ_FIRST_FIELD", "REQ_LAST_FIELD", "REQ_SNEXT_FIELD",
+    "REQ_SPREV_FIELD", "REQ_SFIRST_FIELD", "REQ_SLAST_FIELD", "REQ_LEFT_FIELD",
+    "REQ_RIGHT_FIELD", "REQ_UP_FIELD", "REQ_DOWN_FIELD", "REQ_NEXT_CHAR", "REQ_PREV_CHAR",
+    "REQ_NEXT_LINE", "REQ_PREV_LINE", "REQ_NEXT_WORD", "REQ_PREV_WORD", "REQ_BEG_FIELD",
+    "REQ_END_FIELD", "REQ_BEG_LINE", "REQ_END_LINE", "REQ_LEFT_CHAR", "REQ_RIGHT_CHAR",
+    "REQ_UP_CHAR", "REQ_DOWN_CHAR", "REQ_NEW_LINE", "REQ_INS_CHAR", "REQ_INS_LINE",
+    "REQ_DEL_CHAR", "REQ_DEL_PREV", "REQ_DEL_LINE", "REQ_DEL_WORD", "REQ_CLR_EOL",
+    "REQ_CLR_EOF", "REQ_CLR_FIELD", "REQ_OVL_MODE", "REQ_INS_MODE", "REQ_SCR_FLINE",
+    "REQ_SCR_BLINE", "REQ_SCR_FPAGE", "REQ_SCR_BPAGE", "REQ_SCR_FHPAGE", "REQ_SCR_BHPAGE",
+    "REQ_SCR_FCHAR", "REQ_SCR_BCHAR", "REQ_SCR_HFLINE", "REQ_SCR_HBLINE", "REQ_SCR_HFHALF",
+    "REQ_SCR_HBHALF", "REQ_VALIDATION", "REQ_NEXT_CHOICE", "REQ_PREV_CHOICE",
+];
+
+const FIELD_OPTIONS: &[&str] = &[
+    "O_VISIBLE", "O_ACTIVE", "O_PUBLIC", "O_EDIT", "O_WRAP", "O_BLANK", "O_AUTOSKIP",
+    "O_NULLOK", "O_PASSOK", "O_STATIC",
+];
+
+/* Added in ncurses 6.0 and 6.1; zero, and so no effect, before that. */
+const NEWER_FIELD_OPTIONS: &[&str] = &["O_DYNAMIC_JUSTIFY", "O_NO_LEFT_STRIP"];
+
+fn gen_form_constants(include_paths: &[PathBuf]) {
+    let mut c = String::from(PROBE_HEADER);
+    c.push_str("#include <form.h>\n\nint main(void)\n{\n");
+    emit_ints(&mut c, "c_int", FORM_REQUESTS, None);
+    emit_ints(&mut c, "FieldOptions", FIELD_OPTIONS, None);
+    emit_ints(&mut c, "FieldOptions", NEWER_FIELD_OPTIONS, Some("0"));
+    c.push_str("    return 0;\n}\n");
 
-    std::fs::remove_file(&src).expect(&format!("cannot delete {}", src));
-    std::fs::remove_file(&bin).expect(&format!("cannot delete {}", bin));
+    write_out("form_constants.rs", &run_probe("form_constants", &c, include_paths));
 }
@@ -0,0 +1,263 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: attr.rs
+    Description:
+      Typed video attributes. The bit positions
+      are the same whether chtype is 32 or 64 bits.
+*/
+
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Sub};
+
+use constants::NCURSES_ATTR_SHIFT;
+use ll::{attr_t, chtype};
+
+const fn bits(mask: attr_t, shift: u32) -> attr_t
+{ mask << (shift + NCURSES_ATTR_SHIFT) }
+
+/// A set of video attributes such as `Attr::BOLD | Attr::UNDERLINE`.
+///
+/// The color pair is not part of the set; see `Attr::split`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Attr(attr_t);
+
+/* Names for Debug, in bit order. */
+const NAMES: [(Attr, &str); 16] =
+[
+  (Attr::STANDOUT, "STANDOUT"),
+  (Attr::UNDERLINE, "UNDERLINE"),
+  (Attr::REVERSE, "REVERSE"),
+  (Attr::BLINK, "BLINK"),
+  (Attr::DIM, "DIM"),
+  (Attr::BOLD, "BOLD"),
+  (Attr::ALTCHARSET, "ALTCHARSET"),
+  (Attr::INVIS, "INVIS"),
+  (Attr::PROTECT, "PROTECT"),
+  (Attr::HORIZONTAL, "HORIZONTAL"),
+  (Attr::LEFT, "LEFT"),
+  (Attr::LOW, "LOW"),
+  (Attr::RIGHT, "RIGHT"),
+  (Attr::TOP, "TOP"),
+  (Attr::VERTICAL, "VERTICAL"),
+  (Attr::ITALIC, "ITALIC"),
+];
+
+impl Attr
+{
+  pub const NORMAL: Attr = Attr(0);
+  pub const STANDOUT: Attr = Attr(bits(1, 8));
+  pub const UNDERLINE: Attr = Attr(bits(1, 9));
+  pub const REVERSE: Attr = Attr(bits(1, 10));
+  pub const BLINK: Attr = Attr(bits(1, 11));
+  pub const DIM: Attr = Attr(bits(1, 12));
+  pub const BOLD: Attr = Attr(bits(1, 13));
+  pub const ALTCHARSET: Attr = Attr(bits(1, 14));
+  pub const INVIS: Attr = Attr(bits(1, 15));
+  pub const PROTECT: Attr = Attr(bits(1, 16));
+  pub const HORIZONTAL: Attr = Attr(bits(1, 17));
+  pub const LEFT: Attr = Attr(bits(1, 18));
+  pub const LOW: Attr = Attr(bits(1, 19));
+  pub const RIGHT: Attr = Attr(bits(1, 20));
+  pub const TOP: Attr = Attr(bits(1, 21));
+  pub const VERTICAL: Attr = Attr(bits(1, 22));
+  pub const ITALIC: Attr = Attr(bits(1, 23));
+
+  /// Every attribute bit; the character and color bits are excluded.
+  pub const ALL: Attr = Attr(!(bits(1, 8) - 1));
+
+  /// The bits of a chtype holding the character.
+  pub const CHARTEXT: attr_t = bits(1, 0) - 1;
+  /// The bits of a chtype holding the color pair.
+  pub const COLOR: attr_t = bits((1 << 8) - 1, 0);
+
+  pub const fn empty() -> Attr
+  { Attr::NORMAL }
+
+
+  pub const fn bits(self) -> attr_t
+  { self.0 }
+
+
+  /// Returns `None` if `bits` has character or color bits set.
+  pub fn from_bits(bits: attr_t) -> Option<Attr>
+  {
+    if bits & !Attr::ALL.0 == 0
+    { Some(Attr(bits)) }
+    else
+    { None }
+  }
+
+
+  /// Drops any character or color bits.
+  pub const fn from_bits_truncate(bits: attr_t) -> Attr
+  { Attr(bits & Attr::ALL.0) }
+
+
+  pub const fn is_empty(self) -> bool
+  { self.0 == 0 }
+
+
+  pub const fn contains(self, other: Attr) -> bool
+  { self.0 & other.0 == other.0 }
+
+
+  pub const fn intersects(self, other: Attr) -> bool
+  { self.0 & other.0 != 0 }
+
+
+  pub fn insert(&mut self, other: Attr)
+  { self.0 |= other.0 }
+
+
+  pub fn remove(&mut self, other: Attr)
+  { self.0 &= !other.0 }
+
+
+  pub fn toggle(&mut self, other: Attr)
+  { self.0 ^= other.0 }
+
+
+  /// Splits a chtype into its character, attributes and color pair.
+  pub fn split(ch: chtype) -> (chtype, Attr, i16)
+  {
+    let ch = ch as attr_t;
+    let pair = (ch & Attr::COLOR) >> NCURSES_ATTR_SHIFT;
+    (
+      (ch & Attr::CHARTEXT) as chtype,
+      Attr::from_bits_truncate(ch),
+      pair as i16,
+    )
+  }
+}
+
+impl From<Attr> for attr_t
+{
+  fn from(a: Attr) -> attr_t
+  { a.0 }
+}
+
+impl BitOr for Attr
+{
+  type Output = Attr;
+  fn bitor(self, rhs: Attr) -> Attr
+  { Attr(self.0 | rhs.0) }
+}
+
+impl BitOrAssign for Attr
+{
+  fn bitor_assign(&mut self, rhs: Attr)
+  { self.0 |= rhs.0 }
+}
+
+impl BitAnd for Attr
+{
+  type Output = Attr;
+  fn bitand(self, rhs: Attr) -> Attr
+  { Attr(self.0 & rhs.0) }
+}
+
+impl BitAndAssign for Attr
+{
+  fn bitand_assign(&mut self, rhs: Attr)
+  { self.0 &= rhs.0 }
+}
+
+impl BitXor for Attr
+{
+  type Output = Attr;
+  fn bitxor(self, rhs: Attr) -> Attr
+  { Attr(self.0 ^ rhs.0) }
+}
+
+impl BitXorAssign for Attr
+{
+  fn bitxor_assign(&mut self, rhs: Attr)
+  { self.0 ^= rhs.0 }
+}
+
+impl Sub for Attr
+{
+  type Output = Attr;
+  fn sub(self, rhs: Attr) -> Attr
+  { Attr(self.0 & !rhs.0) }
+}
+
+impl Not for Attr
+{
+  type Output = Attr;
+  fn not(self) -> Attr
+  { Attr(!self.0 & Attr::ALL.0) }
+}
+
+impl fmt::Debug for Attr
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+  {
+    if self.is_empty()
+    { return f.write_str("NORMAL"); }
+
+    let mut rest = *self;
+    let mut first = true;
+    for &(flag, name) in NAMES.iter()
+    {
+      if rest.contains(flag)
+      {
+        if !first
+        { f.write_str(" | ")?; }
+        f.write_str(name)?;
+        rest.remove(flag);
+        first = false;
+      }
+    }
+    if !rest.is_empty()
+    {
+      if !first
+      { f.write_str(" | ")?; }
+      write!(f, "{:#x}", rest.0)?;
+    }
+    Ok(())
+  }
+}
+
+/* The 32-bit layout widens losslessly; attr_t is already u64 with wide_chtype. */
+#[cfg(not(feature="wide_chtype"))]
+impl From<Attr> for u64
+{
+  fn from(a: Attr) -> u64
+  { u64::from(a.0) }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  #[test]
+  fn debug_names_each_attribute()
+  {
+    assert_eq!(format!("{:?}", Attr::NORMAL), "NORMAL");
+    assert_eq!(format!("{:?}", Attr::BOLD), "BOLD");
+    assert_eq!(format!("{:?}", Attr::ITALIC | Attr::STANDOUT | Attr::BOLD), "STANDOUT | BOLD | ITALIC");
+  }
+
+
+  #[test]
+  fn split_separates_character_attributes_and_pair()
+  {
+    let ch = 'x' as attr_t | (Attr::BOLD | Attr::UNDERLINE).bits() | (5 << NCURSES_ATTR_SHIFT);
+    assert_eq!(Attr::split(ch as chtype), ('x' as chtype, Attr::BOLD | Attr::UNDERLINE, 5));
+    assert_eq!(Attr::split('a' as chtype), ('a' as chtype, Attr::NORMAL, 0));
+    assert_eq!(Attr::split((255 << NCURSES_ATTR_SHIFT) as chtype), (0, Attr::NORMAL, 255));
+  }
+
+
+  #[test]
+  fn from_bits_rejects_character_and_color_bits()
+  {
+    assert_eq!(Attr::from_bits('x' as attr_t), None);
+    assert_eq!(Attr::from_bits(Attr::COLOR), None);
+    assert_eq!(Attr::from_bits_truncate('x' as attr_t | Attr::COLOR | Attr::BOLD.bits()), Attr::BOLD);
+  }
+}
@@ -0,0 +1,335 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: checked.rs
+    Description:
+      Result-returning variants of the safe
+      wrappers. Each function has the same name
+      and arguments as its counterpart in the
+      crate root, but turns ERR and null returns
+      into a CursesError naming the failed call.
+*/
+
+#![allow(clippy::too_many_arguments, clippy::ptr_arg)]
+
+use libc::FILE;
+
+use error::{check, check_ptr, CursesError, CursesResult, ErrorKind};
+use super::*;
+
+/* Calls which return OK or ERR. */
+macro_rules! checked {
+  ($($name:ident($($arg:ident: $t:ty),*);)*) => {
+    $(
+      pub fn $name($($arg: $t),*) -> CursesResult<()>
+      { check(stringify!($name), super::$name($($arg),*)) }
+    )*
+  }
+}
+
+/* Calls which move the cursor of `$w` to ($y, $x) first. */
+macro_rules! checked_at {
+  ($($name:ident[$w:expr, $y:ident, $x:ident]($($arg:ident: $t:ty),*);)*) => {
+    $(
+      pub fn $name($($arg: $t),*) -> CursesResult<()>
+      { check_at(stringify!($name), $w, $y, $x, super::$name($($arg),*)) }
+    )*
+  }
+}
+
+/* Calls which return a pointer, null on failure. */
+macro_rules! checked_ptr {
+  ($($name:ident($($arg:ident: $t:ty),*) -> $r:ty;)*) => {
+    $(
+      pub fn $name($($arg: $t),*) -> CursesResult<$r>
+      { check_ptr(stringify!($name), super::$name($($arg),*)) }
+    )*
+  }
+}
+
+/* An ERR from a positioned call is reported as out of bounds when
+ * the position lies outside the window. */
+fn check_at(call: &'static str, w: WINDOW, y: i32, x: i32, rc: i32) -> CursesResult<()>
+{
+  if rc != ERR
+  { return Ok(()); }
+
+  if y < 0 || x < 0 || y >= super::getmaxy(w) || x >= super::getmaxx(w)
+  { Err(CursesError::new(call, ErrorKind::OutOfBounds)) }
+  else
+  { Err(CursesError::new(call, ErrorKind::Failed)) }
+}
+
+checked! {
+  addch(ch: chtype);
+  addchnstr(s: &[chtype], n: i32);
+  addchstr(s: &[chtype]);
+  addnstr(s: &str, n: i32);
+  addstr(s: &str);
+  assume_default_colors(fg: i32, bg: i32);
+  attroff(a: NCURSES_ATTR_T);
+  attron(a: NCURSES_ATTR_T);
+  attrset(a: NCURSES_ATTR_T);
+  attr_get(attrs: &mut attr_t, pair: &mut i16);
+  attr_off(a: attr_t);
+  attr_on(a: attr_t);
+  attr_set(attr: attr_t, pair: i16);
+  beep();
+  bkgd(ch: chtype);
+  border(ls: chtype, rs: chtype, ts: chtype, bs: chtype, tl: chtype, tr: chtype, bl: chtype, br: chtype);
+  box_(w: WINDOW, v: chtype, h: chtype);
+  cbreak();
+  chgat(n: i32, attr: attr_t, color: i16);
+  clear();
+  clearok(w: WINDOW, ok: bool);
+  clrtobot();
+  clrtoeol();
+  color_content(color: i16, r: &mut i16, g: &mut i16, b: &mut i16);
+  color_set(pair: i16);
+  copywin(src_win: WINDOW, dest_win: WINDOW, src_min_row: i32, src_min_col: i32,
+          dest_min_row: i32, dest_min_col: i32, dest_max_row: i32, dest_max_col: i32,
+          overlay: i32);
+  def_prog_mode();
+  def_shell_mode();
+  delay_output(ms: i32);
+  delch();
+  delwin(w: WINDOW);
+  deleteln();
+  doupdate();
+  echo();
+  echochar(c: chtype);
+  erase();
+  endwin();
+  flash();
+  flushinp();
+  halfdelay(tenths: i32);
+  hline(ch: chtype, n: i32);
+  idlok(w: WINDOW, bf: bool);
+  init_color(color: i16, r: i16, g: i16, b: i16);
+  init_pair(pair: i16, f: i16, b: i16);
+  insch(ch: chtype);
+  insdelln(n: i32);
+  insertln();
+  intrflush(w: WINDOW, bf: bool);
+  keypad(w: WINDOW, bf: bool);
+  leaveok(w: WINDOW, bf: bool);
+  meta(w: WINDOW, bf: bool);
+  mvcur(old_y: i32, old_x: i32, new_y: i32, new_x: i32);
+  mvderwin(w: WINDOW, y: i32, x: i32);
+  mvwin(w: WINDOW, y: i32, x: i32);
+  napms(ms: i32);
+  nl();
+  nocbreak();
+  nodelay(w: WINDOW, bf: bool);
+  noecho();
+  nonl();
+  noraw();
+  notimeout(w: WINDOW, bf: bool);
+  overlay(src: WINDOW, dst: WINDOW);
+  overwrite(src: WINDOW, dst: WINDOW);
+  pair_content(pair: i16, f: &mut i16, b: &mut i16);
+  pechochar(pad: WINDOW, ch: chtype);
+  pnoutrefresh(pad: WINDOW, pmin_row: i32, pmin_col: i32, smin_row: i32, smin_col: i32, smax_row: i32, smax_col: i32);
+  prefresh(pad: WINDOW, pmin_row: i32, pmin_col: i32, smin_row: i32, smin_col: i32, smax_row: i32, smax_col: i32);
+  printw(s: &str);
+  putp(s: &str);
+  putwin(w: WINDOW, f: FILE_p);
+  raw();
+  redrawwin(w: WINDOW);
+  refresh();
+  resetty();
+  reset_prog_mode();
+  reset_shell_mode();
+  savetty();
+  scr_dump(filename: &str);
+  scr_init(filename: &str);
+  scrl(n: i32);
+  scroll(w: WINDOW);
+  scrollok(w: WINDOW, bf: bool);
+  scr_restore(filename: &str);
+  scr_set(filename: &str);
+  setscrreg(top: i32, bot: i32);
+  slk_attroff(ch: chtype);
+  slk_attron(ch: chtype);
+  slk_attrset(ch: chtype);
+  slk_attr_set(attrs: attr_t, pair: i16);
+  slk_clear();
+  slk_color(pair: i16);
+  slk_init(fmt: i32);
+  slk_noutrefresh();
+  slk_refresh();
+  slk_restore();
+  slk_set(n: i32, s: &str, fmt: i32);
+  slk_touch();
+  standout();
+  standend();
+  start_color();
+  syncok(w: WINDOW, bf: bool);
+  touchline(w: WINDOW, start: i32, count: i32);
+  touchwin(w: WINDOW);
+  ungetch(ch: i32);
+  untouchwin(w: WINDOW);
+  use_default_colors();
+  vidattr(attrs: chtype);
+  vline(ch: chtype, n: i32);
+  waddch(w: WINDOW, ch: chtype);
+  waddchnstr(w: WINDOW, s: &[chtype], n: i32);
+  waddchstr(w: WINDOW, s: &[chtype]);
+  waddnstr(w: WINDOW, s: &str, n: i32);
+  waddstr(w: WINDOW, s: &str);
+  wattron(w: WINDOW, attr: NCURSES_ATTR_T);
+  wattroff(w: WINDOW, attr: NCURSES_ATTR_T);
+  wattrset(w: WINDOW, attr: NCURSES_ATTR_T);
+  wattr_get(w: WINDOW, attrs: &mut attr_t, pair: &mut i16);
+  wattr_on(w: WINDOW, attr: attr_t);
+  wattr_off(w: WINDOW, attr: attr_t);
+  wattr_set(w: WINDOW, attrs: attr_t, pair: i16);
+  wbkgd(w: WINDOW, ch: chtype);
+  wborder(w: WINDOW, ls: chtype, rs: chtype, ts: chtype, bs: chtype, tl: chtype, tr: chtype, bl: chtype, br: chtype);
+  wchgat(w: WINDOW, n: i32, attr: attr_t, color: i16);
+  wclear(w: WINDOW);
+  wclrtobot(w: WINDOW);
+  wclrtoeol(w: WINDOW);
+  wcolor_set(w: WINDOW, pair: i16);
+  wdelch(w: WINDOW);
+  wdeleteln(w: WINDOW);
+  wechochar(w: WINDOW, ch: chtype);
+  werase(w: WINDOW);
+  whline(w: WINDOW, ch: chtype, n: i32);
+  winsch(w: WINDOW, ch: chtype);
+  winsdelln(w: WINDOW, n: i32);
+  winsertln(w: WINDOW);
+  wnoutrefresh(w: WINDOW);
+  wprintw(w: WINDOW, s: &str);
+  wredrawln(w: WINDOW, start: i32, n: i32);
+  wrefresh(w: WINDOW);
+  wresize(w: WINDOW, lines: i32, cols: i32);
+  wscrl(w: WINDOW, n: i32);
+  wsetscrreg(w: WINDOW, top: i32, bot: i32);
+  wstandout(w: WINDOW);
+  wstandend(w: WINDOW);
+  wtouchln(w: WINDOW, y: i32, n: i32, changed: i32);
+  wvline(w: WINDOW, ch: chtype, n: i32);
+}
+
+checked_at! {
+  mv[stdscr(), y, x](y: i32, x: i32);
+  mvaddch[stdscr(), y, x](y: i32, x: i32, c: chtype);
+  mvaddchnstr[stdscr(), y, x](y: i32, x: i32, s: &[chtype], n: i32);
+  mvaddchstr[stdscr(), y, x](y: i32, x: i32, s: &[chtype]);
+  mvaddnstr[stdscr(), y, x](y: i32, x: i32, s: &str, n: i32);
+  mvaddstr[stdscr(), y, x](y: i32, x: i32, s: &str);
+  mvchgat[stdscr(), y, x](y: i32, x: i32, n: i32, attr: attr_t, color: i16);
+  mvdelch[stdscr(), y, x](y: i32, x: i32);
+  mvhline[stdscr(), y, x](y: i32, x: i32, ch: chtype, n: i32);
+  mvinsch[stdscr(), y, x](y: i32, x: i32, ch: chtype);
+  mvinsnstr[stdscr(), y, x](y: i32, x: i32, s: &str, n: i32);
+  mvinsstr[stdscr(), y, x](y: i32, x: i32, s: &str);
+  mvprintw[stdscr(), y, x](y: i32, x: i32, s: &str);
+  mvvline[stdscr(), y, x](y: i32, x: i32, ch: chtype, n: i32);
+  wmove[w, y, x](w: WINDOW, y: i32, x: i32);
+  mvwaddch[w, y, x](w: WINDOW, y: i32, x: i32, ch: chtype);
+  mvwaddchnstr[w, y, x](w: WINDOW, y: i32, x: i32, s: &[chtype], n: i32);
+  mvwaddchstr[w, y, x](w: WINDOW, y: i32, x: i32, s: &[chtype]);
+  mvwaddnstr[w, y, x](w: WINDOW, y: i32, x: i32, s: &str, n: i32);
+  mvwaddstr[w, y, x](w: WINDOW, y: i32, x: i32, s: &str);
+  mvwchgat[w, y, x](w: WINDOW, y: i32, x: i32, n: i32, attr: attr_t, color: i16);
+  mvwdelch[w, y, x](w: WINDOW, y: i32, x: i32);
+  mvwhline[w, y, x](w: WINDOW, y: i32, x: i32, ch: chtype, n: i32);
+  mvwinsch[w, y, x](w: WINDOW, y: i32, x: i32, ch: chtype);
+  mvwinsnstr[w, y, x](w: WINDOW, y: i32, x: i32, s: &str, n: i32);
+  mvwinsstr[w, y, x](w: WINDOW, y: i32, x: i32, s: &str);
+  mvwprintw[w, y, x](w: WINDOW, y: i32, x: i32, s: &str);
+  mvwvline[w, y, x](w: WINDOW, y: i32, x: i32, ch: chtype, n: i32);
+}
+
+checked_ptr! {
+  derwin(w: WINDOW, lines: i32, cols: i32, y: i32, x: i32) -> WINDOW;
+  dupwin(w: WINDOW) -> WINDOW;
+  getwin(reader: *mut FILE) -> WINDOW;
+  initscr() -> WINDOW;
+  newpad(lines: i32, cols: i32) -> WINDOW;
+  newterm(ty: Option<&str>, out_fd: FILE_p, in_fd: FILE_p) -> SCREEN;
+  newwin(lines: i32, cols: i32, y: i32, x: i32) -> WINDOW;
+  subpad(w: WINDOW, lines: i32, cols: i32, y: i32, x: i32) -> WINDOW;
+  subwin(w: WINDOW, lines: i32, cols: i32, y: i32, x: i32) -> WINDOW;
+}
+
+/*
+ * Panels.
+ */
+
+#[cfg(feature="panel")]
+checked! {
+  hide_panel(panel: PANEL);
+  show_panel(panel: PANEL);
+  del_panel(panel: PANEL);
+  top_panel(panel: PANEL);
+  bottom_panel(panel: PANEL);
+  move_panel(panel: PANEL, y: i32, x: i32);
+  replace_panel(panel: PANEL, window: WINDOW);
+}
+
+#[cfg(feature="panel")]
+checked_ptr! {
+  new_panel(window: WINDOW) -> PANEL;
+}
+
+/*
+ * Menus.
+ */
+
+#[cfg(feature="menu")]
+macro_rules! checked_menu {
+  ($($name:ident($($arg:ident: $t:ty),*);)*) => {
+    $(
+      pub fn $name($($arg: $t),*) -> CursesResult<()>
+      { ::error::check_menu(stringify!($name), super::$name($($arg),*)) }
+    )*
+  }
+}
+
+#[cfg(feature="menu")]
+checked_menu! {
+  free_menu(menu: MENU);
+  item_opts_off(item: ITEM, opts: i32);
+  item_opts_on(item: ITEM, opts: i32);
+  menu_driver(menu: MENU, c: i32);
+  menu_opts_off(menu: MENU, opts: i32);
+  menuopts_on(menu: MENU, opts: i32);
+  pos_menu_cursor(menu: MENU);
+  post_menu(menu: MENU);
+  scale_menu(menu: MENU, rows: &mut i32, cols: &mut i32);
+  set_current_item(menu: MENU, item: ITEM);
+  set_item_init(menu: MENU, hook: HOOK);
+  set_item_opts(item: ITEM, opts: i32);
+  set_item_term(menu: MENU, hook: HOOK);
+  set_item_value(item: ITEM, value: bool);
+  set_menu_back(menu: MENU, attr: chtype);
+  set_menu_fore(menu: MENU, attr: chtype);
+  set_menu_grey(menu: MENU, attr: chtype);
+  set_menu_format(menu: MENU, rows: i32, cols: i32);
+  set_menu_init(menu: MENU, hook: HOOK);
+  set_menu_items(menu: MENU, items: &mut Vec<ITEM>);
+  set_menu_mark(menu: MENU, mark: &str);
+  set_menu_opts(menu: MENU, opts: i32);
+  set_menu_pad(menu: MENU, opts: i32);
+  set_menu_pattern(menu: MENU, pattern: &str);
+  set_menu_spacing(menu: MENU, spc_description: i32, spc_rows: i32, spc_columns: i32);
+  set_menu_sub(menu: MENU, win: WINDOW);
+  set_menu_term(menu: MENU, hook: HOOK);
+  set_menu_win(menu: MENU, win: WINDOW);
+  set_top_row(menu: MENU, row: i32);
+  unpost_menu(menu: MENU);
+  menu_spacing(menu: MENU, spc_description: &mut i32, spc_rows: &mut i32, spc_columns: &mut i32);
+}
+
+#[cfg(feature="menu")]
+pub fn new_menu(items: &mut Vec<ITEM>) -> CursesResult<MENU>
+{ check_ptr("new_menu", super::new_menu(items)) }
+
+
+#[cfg(feature="menu")]
+pub fn new_item(name: &str, description: &str) -> CursesResult<ITEM>
+{ check_ptr("new_item", super::new_item(name, description)) }
@@ -0,0 +1,300 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: curses.rs
+    Description:
+      A context token proving curses is set up,
+      held by one value on one thread.
+*/
+
+use std::io;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::os::unix::io::AsRawFd;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ll;
+use constants::{self, TRUE};
+use error::{CursesError, ErrorKind};
+use key::Key;
+use mouse::{self, MouseEvent, MouseMask};
+use screen::Screen;
+use session::Session;
+use window::Window;
+use super::CURSOR_VISIBILITY;
+
+/* Set while a context exists anywhere in the process. */
+static HELD: AtomicBool = AtomicBool::new(false);
+
+enum Backing
+{
+  Session(Session),
+  Screen(Screen),
+}
+
+/// The right to make curses calls.
+///
+/// Only one context exists at a time, and it can't leave the thread that
+/// made it. Windows made through it borrow it, so drawing before setup,
+/// after teardown or from another thread does not compile:
+///
+/// ```compile_fail
+/// # use ncurses::*;
+/// let curses = Curses::initscr().unwrap();
+/// std::thread::spawn(move || curses.refresh());
+/// ```
+///
+/// ```no_run
+/// # use ncurses::*;
+/// let curses = Curses::initscr().unwrap();
+/// let win = curses.newwin(5, 20, 0, 0).unwrap();
+/// win.addstr("hello");
+/// win.refresh();
+/// curses.getch();
+/// ```
+///
+/// A context made by `initscr` acts on the current screen, like the
+/// other functions in this crate. One made by `newterm` acts on its own
+/// screen whichever is current, through `Screen`'s `*_sp` calls.
+pub struct Curses
+{
+  /* Dropped by hand, so the context is only released once the terminal
+     has been given back. */
+  backing: ManuallyDrop<Backing>,
+  /* Curses calls are not thread-safe. */
+  _not_send: PhantomData<*mut ()>,
+}
+
+/* Claims the context for the caller, or fails naming `call`. */
+fn claim(call: &'static str) -> io::Result<()>
+{
+  if HELD.swap(true, Ordering::SeqCst)
+  { Err(io::Error::other(CursesError::new(call, ErrorKind::AlreadyInitialized))) }
+  else
+  { Ok(()) }
+}
+
+
+fn release()
+{ HELD.store(false, Ordering::SeqCst); }
+
+impl Curses
+{
+  /// Calls `initscr` through a `Session`, so the terminal is restored on
+  /// drop and panic. Fails with a `CursesError` of kind
+  /// `AlreadyInitialized` while another context or session exists.
+  pub fn initscr() -> io::Result<Curses>
+  {
+    claim("initscr")?;
+    match Session::init()
+    {
+      Some(session) => Ok(Curses { backing: ManuallyDrop::new(Backing::Session(session)), _not_send: PhantomData }),
+      None =>
+      {
+        release();
+        Err(io::Error::other(CursesError::new("initscr", ErrorKind::AlreadyInitialized)))
+      }
+    }
+  }
+
+
+  /// Calls `newterm` on the terminal behind `input` and `output`; see
+  /// `Screen::new`.
+  pub fn newterm<I: AsRawFd, O: AsRawFd>(ty: Option<&str>, input: &I, output: &O) -> io::Result<Curses>
+  {
+    claim("newterm")?;
+    match Screen::new(ty, input, output)
+    {
+      Ok(screen) => Ok(Curses { backing: ManuallyDrop::new(Backing::Screen(screen)), _not_send: PhantomData }),
+      Err(e) =>
+      {
+        release();
+        Err(e)
+      }
+    }
+  }
+
+
+  /// The session behind a context made by `initscr`, for `handle_signals`.
+  pub fn session(&mut self) -> Option<&mut Session>
+  {
+    match *self.backing
+    {
+      Backing::Session(ref mut s) => Some(s),
+      Backing::Screen(_) => None,
+    }
+  }
+
+
+  /// The screen behind a context made by `newterm`.
+  pub fn screen(&self) -> Option<&Screen>
+  {
+    match *self.backing
+    {
+      Backing::Screen(ref s) => Some(s),
+      Backing::Session(_) => None,
+    }
+  }
+
+
+  /* Runs `own` on the context's screen, or `global` for one made by
+     initscr. */
+  fn route<'a, T, S, G>(&'a self, own: S, global: G) -> T
+    where S: FnOnce(&'a Screen) -> T, G: FnOnce() -> T
+  {
+    match *self.backing
+    {
+      Backing::Screen(ref s) => own(s),
+      Backing::Session(_) => global(),
+    }
+  }
+
+  /* Windows. */
+
+  pub fn stdscr(&self) -> Window<'_>
+  { self.route(|s| s.stdscr(), || Window::borrowed(constants::stdscr())) }
+
+
+  pub fn newwin(&self, lines: i32, cols: i32, begin_y: i32, begin_x: i32) -> Option<Window<'_>>
+  { self.route(|s| s.newwin(lines, cols, begin_y, begin_x), || Window::from_ptr(unsafe { ll::newwin(lines, cols, begin_y, begin_x) })) }
+
+
+  pub fn newpad(&self, lines: i32, cols: i32) -> Option<Window<'_>>
+  { self.route(|s| s.newpad(lines, cols), || Window::from_ptr(unsafe { ll::newpad(lines, cols) })) }
+
+
+  pub fn lines(&self) -> i32
+  { self.route(|s| s.size().0, constants::LINES) }
+
+
+  pub fn cols(&self) -> i32
+  { self.route(|s| s.size().1, constants::COLS) }
+
+  /* Output. */
+
+  pub fn refresh(&self) -> i32
+  { self.stdscr().refresh() }
+
+
+  pub fn doupdate(&self) -> i32
+  { self.route(Screen::doupdate, || unsafe { ll::doupdate() }) }
+
+
+  pub fn beep(&self) -> i32
+  { self.route(Screen::beep, || unsafe { ll::beep() }) }
+
+
+  pub fn flash(&self) -> i32
+  { self.route(Screen::flash, || unsafe { ll::flash() }) }
+
+
+  pub fn curs_set(&self, visibility: CURSOR_VISIBILITY) -> Option<CURSOR_VISIBILITY>
+  { self.route(|s| s.curs_set(visibility), || super::curs_set(visibility)) }
+
+
+  pub fn napms(&self, ms: i32) -> i32
+  { self.route(|s| s.napms(ms), || unsafe { ll::napms(ms) }) }
+
+  /* Modes. */
+
+  pub fn cbreak(&self) -> i32
+  { self.route(Screen::cbreak, super::cbreak) }
+
+
+  pub fn nocbreak(&self) -> i32
+  { self.route(Screen::nocbreak, super::nocbreak) }
+
+
+  pub fn raw(&self) -> i32
+  { self.route(Screen::raw, super::raw) }
+
+
+  pub fn noraw(&self) -> i32
+  { self.route(Screen::noraw, super::noraw) }
+
+
+  pub fn echo(&self) -> i32
+  { self.route(Screen::echo, super::echo) }
+
+
+  pub fn noecho(&self) -> i32
+  { self.route(Screen::noecho, super::noecho) }
+
+
+  pub fn nl(&self) -> i32
+  { self.route(Screen::nl, || unsafe { ll::nl() }) }
+
+
+  pub fn nonl(&self) -> i32
+  { self.route(Screen::nonl, || unsafe { ll::nonl() }) }
+
+
+  pub fn halfdelay(&self, tenths: i32) -> i32
+  { self.route(|s| s.halfdelay(tenths), || unsafe { ll::halfdelay(tenths) }) }
+
+  /* Color. */
+
+  pub fn has_colors(&self) -> bool
+  { self.route(Screen::has_colors, || unsafe { ll::has_colors() == TRUE }) }
+
+
+  pub fn start_color(&self) -> i32
+  { self.route(Screen::start_color, || unsafe { ll::start_color() }) }
+
+
+  pub fn use_default_colors(&self) -> i32
+  { self.route(Screen::use_default_colors, || unsafe { ll::use_default_colors() }) }
+
+
+  pub fn init_pair(&self, pair: i16, f: i16, b: i16) -> i32
+  { self.route(|s| s.init_pair(pair, f, b), || unsafe { ll::init_pair(pair, f, b) }) }
+
+
+  pub fn init_color(&self, color: i16, r: i16, g: i16, b: i16) -> i32
+  { self.route(|s| s.init_color(color, r, g, b), || unsafe { ll::init_color(color, r, g, b) }) }
+
+  /* Input. */
+
+  pub fn getch(&self) -> i32
+  { self.stdscr().getch() }
+
+
+  /// Reads a key from `stdscr`; see `Window::get_key`.
+  pub fn get_key(&self) -> Option<Key>
+  { self.stdscr().get_key() }
+
+
+  pub fn ungetch(&self, ch: i32) -> i32
+  { self.route(|s| s.ungetch(ch), || unsafe { ll::ungetch(ch) }) }
+
+
+  pub fn flushinp(&self) -> i32
+  { self.route(Screen::flushinp, || unsafe { ll::flushinp() }) }
+
+
+  pub fn mousemask(&self, mask: MouseMask) -> MouseMask
+  { self.route(|s| s.mousemask(mask), || mask.apply()) }
+
+
+  pub fn getmouse(&self) -> Option<MouseEvent>
+  { self.route(Screen::getmouse, mouse::getmouse) }
+
+
+  pub fn resizeterm(&self, lines: i32, cols: i32) -> i32
+  { self.route(|s| s.resizeterm(lines, cols), || unsafe { ll::resizeterm(lines, cols) }) }
+
+
+  /// Leaves curses mode for a while; the next `refresh` resumes it.
+  pub fn endwin(&self) -> i32
+  { self.route(Screen::endwin, || unsafe { ll::endwin() }) }
+}
+
+impl Drop for Curses
+{
+  fn drop(&mut self)
+  {
+    unsafe { ManuallyDrop::drop(&mut self.backing); }
+    release();
+  }
+}
@@ -0,0 +1,116 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: error.rs
+    Description:
+      Typed errors for the Result-returning
+      wrappers in the checked module.
+*/
+
+use std::error::Error;
+use std::fmt;
+
+use libc::c_int;
+use constants::ERR;
+use menu::wrapper::MenuCode;
+
+/// Why a curses call failed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ErrorKind
+{
+  /// The call returned `ERR`.
+  Failed,
+  /// The call returned a null pointer.
+  Null,
+  /// The requested position lies outside the window.
+  OutOfBounds,
+  /// The name is not a terminfo capability of the requested type.
+  NotCapability,
+  /// No terminfo entry exists for the terminal type.
+  NoSuchTerminal,
+  /// The terminal is hardcopy and can't be driven by curses.
+  HardcopyTerminal,
+  /// The terminfo database could not be found.
+  NoTerminfoDatabase,
+  /// Curses is already set up, by this thread or another.
+  AlreadyInitialized,
+  /// The menu library returned an `E_*` code.
+  Menu(MenuCode),
+}
+
+/// A failed curses call, naming the function and the reason.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CursesError
+{
+  call: &'static str,
+  kind: ErrorKind,
+}
+
+pub type CursesResult<T> = Result<T, CursesError>;
+
+impl CursesError
+{
+  pub fn new(call: &'static str, kind: ErrorKind) -> CursesError
+  { CursesError { call, kind } }
+
+
+  /// The name of the ncurses function which failed.
+  pub fn call(&self) -> &'static str
+  { self.call }
+
+
+  pub fn kind(&self) -> ErrorKind
+  { self.kind }
+}
+
+impl fmt::Display for CursesError
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+  {
+    match self.kind
+    {
+      ErrorKind::Failed => write!(f, "{} failed", self.call),
+      ErrorKind::Null => write!(f, "{} returned null", self.call),
+      ErrorKind::OutOfBounds => write!(f, "{} out of bounds", self.call),
+      ErrorKind::NotCapability => write!(f, "{}: no such capability of that type", self.call),
+      ErrorKind::NoSuchTerminal => write!(f, "{}: unknown terminal type", self.call),
+      ErrorKind::HardcopyTerminal => write!(f, "{}: hardcopy terminal", self.call),
+      ErrorKind::NoTerminfoDatabase => write!(f, "{}: terminfo database not found", self.call),
+      ErrorKind::AlreadyInitialized => write!(f, "{}: curses is already initialised", self.call),
+      ErrorKind::Menu(code) => write!(f, "{} failed: {}", self.call, code.description()),
+    }
+  }
+}
+
+impl Error for CursesError {}
+
+/* Conversions from the raw return values. */
+
+pub fn check(call: &'static str, rc: c_int) -> CursesResult<()>
+{
+  if rc == ERR
+  { Err(CursesError::new(call, ErrorKind::Failed)) }
+  else
+  { Ok(()) }
+}
+
+
+pub fn check_ptr<T>(call: &'static str, p: *mut T) -> CursesResult<*mut T>
+{
+  if p.is_null()
+  { Err(CursesError::new(call, ErrorKind::Null)) }
+  else
+  { Ok(p) }
+}
+
+
+#[cfg(feature="menu")]
+pub fn check_menu(call: &'static str, rc: c_int) -> CursesResult<()>
+{
+  match MenuCode::from(rc)
+  {
+    MenuCode::Ok => Ok(()),
+    code => Err(CursesError::new(call, ErrorKind::Menu(code))),
+  }
+}
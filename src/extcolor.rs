@@ -0,0 +1,162 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: extcolor.rs
+    Description:
+      Extended color pairs and colors (ncurses 6.1),
+      numbered past the 16-bit limit of init_pair.
+*/
+
+#![allow(clippy::too_many_arguments)]
+
+use std::mem;
+
+use libc::{c_int, c_void};
+use ll;
+use super::*;
+
+/* The `opts` argument through which the opaque-pair calls take an int pair. */
+fn pair_opt(pair: &i32) -> *mut c_void
+{ pair as *const i32 as *mut c_void }
+
+
+pub fn init_extended_pair(pair: i32, f: i32, b: i32) -> i32
+{ unsafe { ll::init_extended_pair(pair, f, b) } }
+
+
+pub fn init_extended_color(color: i32, r: i32, g: i32, b: i32) -> i32
+{ unsafe { ll::init_extended_color(color, r, g, b) } }
+
+
+pub fn extended_pair_content(pair: i32, f: &mut i32, b: &mut i32) -> i32
+{ unsafe { ll::extended_pair_content(pair, f as *mut c_int, b as *mut c_int) } }
+
+
+pub fn extended_color_content(color: i32, r: &mut i32, g: &mut i32, b: &mut i32) -> i32
+{
+  unsafe
+  {
+    ll::extended_color_content(color,
+                               r as *mut c_int,
+                               g as *mut c_int,
+                               b as *mut c_int)
+  }
+}
+
+
+pub fn extended_slk_color(pair: i32) -> i32
+{ unsafe { ll::extended_slk_color(pair) } }
+
+
+/// Returns a pair for the given foreground and background, defining one
+/// if needed; `ERR` when every pair is taken.
+pub fn alloc_pair(f: i32, b: i32) -> i32
+{ unsafe { ll::alloc_pair(f, b) } }
+
+
+/// Returns the pair already defined for the given colors, or `ERR`.
+pub fn find_pair(f: i32, b: i32) -> i32
+{ unsafe { ll::find_pair(f, b) } }
+
+
+pub fn free_pair(pair: i32) -> i32
+{ unsafe { ll::free_pair(pair) } }
+
+
+pub fn reset_color_pairs()
+{ unsafe { ll::reset_color_pairs() } }
+
+
+/* Opaque-pair attribute calls: the pair is an int, not part of the attributes. */
+
+pub fn extended_attr_get(attrs: &mut attr_t, pair: &mut i32) -> i32
+{
+  let mut short_pair = 0;
+  unsafe { ll::attr_get(attrs, &mut short_pair, pair as *mut i32 as *mut c_void) }
+}
+
+
+pub fn extended_wattr_get(w: WINDOW, attrs: &mut attr_t, pair: &mut i32) -> i32
+{
+  let mut short_pair = 0;
+  unsafe { ll::wattr_get(w, attrs, &mut short_pair, pair as *mut i32 as *mut c_void) }
+}
+
+
+pub fn extended_attr_set(attrs: attr_t, pair: i32) -> i32
+{ unsafe { ll::attr_set(attrs, 0, pair_opt(&pair)) } }
+
+
+pub fn extended_wattr_set(w: WINDOW, attrs: attr_t, pair: i32) -> i32
+{ unsafe { ll::wattr_set(w, attrs, 0, pair_opt(&pair)) } }
+
+
+pub fn extended_color_set(pair: i32) -> i32
+{ unsafe { ll::color_set(0, pair_opt(&pair)) } }
+
+
+pub fn extended_wcolor_set(w: WINDOW, pair: i32) -> i32
+{ unsafe { ll::wcolor_set(w, 0, pair_opt(&pair)) } }
+
+
+pub fn extended_chgat(n: i32, attrs: attr_t, pair: i32) -> i32
+{ unsafe { ll::chgat(n, attrs, 0, pair_opt(&pair)) } }
+
+
+pub fn extended_wchgat(w: WINDOW, n: i32, attrs: attr_t, pair: i32) -> i32
+{ unsafe { ll::wchgat(w, n, attrs, 0, pair_opt(&pair)) } }
+
+
+pub fn extended_mvchgat(y: i32, x: i32, n: i32, attrs: attr_t, pair: i32) -> i32
+{ unsafe { ll::mvchgat(y, x, n, attrs, 0, pair_opt(&pair)) } }
+
+
+pub fn extended_mvwchgat(w: WINDOW, y: i32, x: i32, n: i32, attrs: attr_t, pair: i32) -> i32
+{ unsafe { ll::mvwchgat(w, y, x, n, attrs, 0, pair_opt(&pair)) } }
+
+
+pub fn extended_slk_attr_set(attrs: attr_t, pair: i32) -> i32
+{ unsafe { ll::slk_attr_set(attrs, 0, pair_opt(&pair)) } }
+
+
+/// `setcchar` with an int color pair.
+pub fn extended_setcchar(wch: &str, attrs: attr_t, pair: i32) -> Option<cchar_t>
+{
+  if wch.chars().count() > ll::CCHARW_MAX
+  { return None; }
+
+  let buf = wide::to_wide(wch);
+  unsafe
+  {
+    let mut wcval: cchar_t = mem::zeroed();
+    match ll::setcchar(&mut wcval, buf.as_ptr(), attrs, 0, pair_opt(&pair))
+    {
+      ERR => None,
+      _ => Some(wcval),
+    }
+  }
+}
+
+
+impl<'a> Window<'a>
+{
+  pub fn extended_attr_get(&self, attrs: &mut attr_t, pair: &mut i32) -> i32
+  { extended_wattr_get(self.as_raw(), attrs, pair) }
+
+
+  pub fn extended_attr_set(&self, attrs: attr_t, pair: i32) -> i32
+  { extended_wattr_set(self.as_raw(), attrs, pair) }
+
+
+  pub fn extended_color_set(&self, pair: i32) -> i32
+  { extended_wcolor_set(self.as_raw(), pair) }
+
+
+  pub fn extended_chgat(&self, n: i32, attrs: attr_t, pair: i32) -> i32
+  { extended_wchgat(self.as_raw(), n, attrs, pair) }
+
+
+  pub fn extended_mvchgat(&self, y: i32, x: i32, n: i32, attrs: attr_t, pair: i32) -> i32
+  { extended_mvwchgat(self.as_raw(), y, x, n, attrs, pair) }
+}
@@ -9,8 +9,8 @@ pub type FIELD = *mut i8;
 pub type FIELDTYPE = *mut i8;
 pub type FieldOptions = c_int;
 
-#[cfg(feature="form")] #[link(name="form")]
-extern {
+#[cfg(feature="form")]
+extern "C" {
     pub fn set_current_field(_:FORM, _:FIELD) -> c_int;
     pub fn current_field(_:FORM) -> FIELD;
     pub fn unfocus_current_field(_:FORM) -> c_int;
@@ -18,8 +18,8 @@ extern {
     pub fn form_page(_:FORM) -> c_int;
     pub fn field_index(_:FIELD) -> c_int;
 
-    pub fn data_ahead(_:FORM) -> c_int;
-    pub fn data_behind(_:FORM) -> c_int;
+    pub fn data_ahead(_:FORM) -> c_bool;
+    pub fn data_behind(_:FORM) -> c_bool;
 
     pub fn new_field(_:c_int, _:c_int, _:c_int, _:c_int, _:c_int, _:c_int) -> FIELD;
     pub fn dup_field(_:FIELD, _:c_int, _:c_int) -> FIELD;
@@ -34,7 +34,7 @@ extern {
     // pub fn void *field_arg(const FIELD *field); TODO
 
     pub fn set_field_buffer(_:FIELD, _:c_int, _:*const c_char) -> c_int;
-    pub fn field_buffer(_:FIELD, _:c_int) -> *const c_char;
+    pub fn field_buffer(_:FIELD, _:c_int) -> *mut c_char;
     pub fn set_field_status(_:FIELD, _:c_bool) -> c_int;
     pub fn field_status(_:FIELD) -> c_bool;
     pub fn set_max_field(_:FIELD, _:c_int) -> c_int;
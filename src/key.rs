@@ -0,0 +1,308 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: key.rs
+    Description:
+      A typed view of the values returned by
+      getch and get_wch.
+*/
+
+use std::char;
+use std::ffi::CStr;
+use std::fmt;
+use std::str::FromStr;
+
+use ll;
+use constants::*;
+use super::WchResult;
+
+/// A key press, decoded from `getch` or `get_wch`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Key
+{
+  /// A character, including control characters such as `'\x18'` for C-x.
+  Char(char),
+  Up,
+  Down,
+  Left,
+  Right,
+  Home,
+  End,
+  Backspace,
+  Enter,
+  Delete,
+  Insert,
+  PageUp,
+  PageDown,
+  BackTab,
+  Begin,
+  Print,
+  Break,
+  ScrollForward,
+  ScrollBackward,
+  ShiftLeft,
+  ShiftRight,
+  ShiftHome,
+  ShiftEnd,
+  ShiftDelete,
+  ShiftInsert,
+  /// Function key `F(n)`, for n in 0..64.
+  F(u8),
+  /// The terminal was resized.
+  Resize,
+  /// A mouse event is waiting for `getmouse`.
+  Mouse,
+  Event,
+  /// A key code without a variant of its own.
+  Unknown(i32),
+}
+
+/* Number of function keys ncurses reserves codes for. */
+const F_KEYS: i32 = 64;
+
+const NAMED: [(Key, i32); 27] =
+[
+  (Key::Up, KEY_UP),
+  (Key::Down, KEY_DOWN),
+  (Key::Left, KEY_LEFT),
+  (Key::Right, KEY_RIGHT),
+  (Key::Home, KEY_HOME),
+  (Key::End, KEY_END),
+  (Key::Backspace, KEY_BACKSPACE),
+  (Key::Enter, KEY_ENTER),
+  (Key::Delete, KEY_DC),
+  (Key::Insert, KEY_IC),
+  (Key::PageUp, KEY_PPAGE),
+  (Key::PageDown, KEY_NPAGE),
+  (Key::BackTab, KEY_BTAB),
+  (Key::Begin, KEY_BEG),
+  (Key::Print, KEY_PRINT),
+  (Key::Break, KEY_BREAK),
+  (Key::ScrollForward, KEY_SF),
+  (Key::ScrollBackward, KEY_SR),
+  (Key::ShiftLeft, KEY_SLEFT),
+  (Key::ShiftRight, KEY_SRIGHT),
+  (Key::ShiftHome, KEY_SHOME),
+  (Key::ShiftEnd, KEY_SEND),
+  (Key::ShiftDelete, KEY_SDC),
+  (Key::ShiftInsert, KEY_SIC),
+  (Key::Resize, KEY_RESIZE),
+  (Key::Mouse, KEY_MOUSE),
+  (Key::Event, KEY_EVENT),
+];
+
+impl Key
+{
+  /// Decodes a `getch`/`wgetch` result; `None` for `ERR`.
+  ///
+  /// Bytes above 0x7f are taken as Latin-1, since `getch` does not
+  /// decode multibyte characters; use `get_wch` for those.
+  pub fn from_getch(ch: i32) -> Option<Key>
+  {
+    match ch
+    {
+      ERR => None,
+      0..=0xff => Some(Key::Char(char::from(ch as u8))),
+      _ => Some(Key::from_code(ch)),
+    }
+  }
+
+
+  /// Maps a `KEY_*` code to its key.
+  pub fn from_code(code: i32) -> Key
+  {
+    if (KEY_F0..KEY_F0 + F_KEYS).contains(&code)
+    { return Key::F((code - KEY_F0) as u8); }
+
+    NAMED.iter()
+         .find(|&&(_, c)| c == code)
+         .map_or(Key::Unknown(code), |&(k, _)| k)
+  }
+
+
+  /// The value `getch` would return for this key.
+  pub fn code(self) -> i32
+  {
+    match self
+    {
+      Key::Char(c) => c as i32,
+      Key::F(n) => KEY_F0 + i32::from(n),
+      Key::Unknown(code) => code,
+      key => NAMED.iter()
+                  .find(|&&(k, _)| k == key)
+                  .map_or(ERR, |&(_, c)| c),
+    }
+  }
+
+
+  /// Whether this is a key code rather than a character.
+  pub fn is_code(self) -> bool
+  { !matches!(self, Key::Char(_)) }
+}
+
+impl From<WchResult> for Key
+{
+  fn from(wch: WchResult) -> Key
+  {
+    match wch
+    {
+      WchResult::Char(c) => char::from_u32(c).map_or(Key::Unknown(c as i32), Key::Char),
+      WchResult::KeyCode(code) => Key::from_code(code),
+    }
+  }
+}
+
+/* keyname(3X), which returns null for values it has no name for. */
+fn keyname(code: i32) -> Option<String>
+{
+  unsafe
+  {
+    let name = ll::keyname(code);
+    if name.is_null()
+    { None }
+    else
+    { Some(CStr::from_ptr(name).to_string_lossy().into_owned()) }
+  }
+}
+
+/// Formats the key as `keyname` does: `KEY_UP`, `KEY_F(5)`, `^X`, `a`.
+/// Characters outside ASCII are written as themselves.
+impl fmt::Display for Key
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+  {
+    match *self
+    {
+      Key::Char(c) if !c.is_ascii() => write!(f, "{}", c),
+      key =>
+      {
+        match keyname(key.code())
+        {
+          Some(name) => f.write_str(&name),
+          None => write!(f, "UNKNOWN({})", key.code()),
+        }
+      }
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseKeyError(String);
+
+impl fmt::Display for ParseKeyError
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+  { write!(f, "unknown key name \"{}\"", self.0) }
+}
+
+impl ::std::error::Error for ParseKeyError {}
+
+/* The control character for `c`, as typed with the control key held. */
+fn control(c: char) -> Option<char>
+{
+  match c
+  {
+    '?' => Some('\x7f'),
+    '@'..='_' => Some(((c as u8) & 0x1f) as char),
+    'a'..='z' => Some(((c as u8) & 0x1f) as char),
+    _ => None,
+  }
+}
+
+/// Parses a single character, `C-x` or `^X` for control characters,
+/// `KEY_F(n)`, or any `KEY_*` name known to `keyname`.
+impl FromStr for Key
+{
+  type Err = ParseKeyError;
+
+  fn from_str(s: &str) -> Result<Key, ParseKeyError>
+  {
+    let err = || ParseKeyError(s.to_string());
+    let mut chars = s.chars();
+
+    match (chars.next(), chars.next(), chars.next(), chars.next())
+    {
+      (Some(c), None, _, _) => return Ok(Key::Char(c)),
+      (Some('^'), Some(c), None, _) => return control(c).map(Key::Char).ok_or_else(err),
+      (Some('C'), Some('-'), Some(c), None) => return control(c).map(Key::Char).ok_or_else(err),
+      _ => {}
+    }
+
+    if s.starts_with("KEY_F(") && s.ends_with(')')
+    {
+      return match s["KEY_F(".len()..s.len() - 1].parse::<u8>()
+      {
+        Ok(n) if i32::from(n) < F_KEYS => Ok(Key::F(n)),
+        _ => Err(err()),
+      };
+    }
+
+    if s.starts_with("KEY_")
+    {
+      for code in KEY_MIN..=KEY_MAX
+      {
+        if keyname(code).as_deref() == Some(s)
+        { return Ok(Key::from_code(code)); }
+      }
+    }
+
+    Err(err())
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  fn parse(s: &str) -> Result<Key, ParseKeyError>
+  { s.parse() }
+
+
+  #[test]
+  fn parses_characters_and_control_keys()
+  {
+    assert_eq!(parse("a"), Ok(Key::Char('a')));
+    assert_eq!(parse("é"), Ok(Key::Char('é')));
+    assert_eq!(parse("^"), Ok(Key::Char('^')));
+    assert_eq!(parse("C-x"), Ok(Key::Char('\x18')));
+    assert_eq!(parse("C-X"), Ok(Key::Char('\x18')));
+    assert_eq!(parse("^X"), Ok(Key::Char('\x18')));
+    assert_eq!(parse("^["), Ok(Key::Char('\x1b')));
+    assert_eq!(parse("^?"), Ok(Key::Char('\x7f')));
+  }
+
+
+  #[test]
+  fn parses_function_keys()
+  {
+    assert_eq!(parse("KEY_F(0)"), Ok(Key::F(0)));
+    assert_eq!(parse("KEY_F(12)"), Ok(Key::F(12)));
+    assert_eq!(parse("KEY_F(63)"), Ok(Key::F(63)));
+  }
+
+
+  #[test]
+  fn parses_keyname_names()
+  {
+    assert_eq!(parse("KEY_UP"), Ok(Key::Up));
+    assert_eq!(parse("KEY_BACKSPACE"), Ok(Key::Backspace));
+    assert_eq!(parse("KEY_NPAGE"), Ok(Key::PageDown));
+    assert_eq!(parse("KEY_RESIZE"), Ok(Key::Resize));
+  }
+
+
+  #[test]
+  fn rejects_unknown_names()
+  {
+    for s in &["", "ab", "C-", "C-1", "^1", "^xy", "KEY_F(64)", "KEY_F(-1)", "KEY_F()", "KEY_F(x)",
+               "KEY_NOPE", "KEY_up", "UP"]
+    { assert_eq!(parse(s), Err(ParseKeyError(s.to_string())), "{:?}", s); }
+  }
+
+
+  #[test]
+  fn error_names_the_input()
+  { assert_eq!(parse("KEY_NOPE").unwrap_err().to_string(), "unknown key name \"KEY_NOPE\""); }
+}
@@ -0,0 +1,188 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: line.rs
+    Description:
+      Line editing behind the getstr family. ncurses'
+      own wgetnstr writes into a fixed byte buffer and
+      counts bytes; this reads characters instead.
+*/
+
+use ll::{self, WINDOW};
+use constants::*;
+use error::{CursesError, CursesResult, ErrorKind};
+use super::{beep, wclrtoeol, wmove, getcurx, getcury};
+
+#[cfg(feature="mode_queries")]
+extern "C" {
+  fn is_cbreak() -> ::libc::c_int;
+}
+
+enum Input
+{
+  Char(char),
+  Key(i32),
+}
+
+#[cfg(feature="wide")]
+fn read_input(w: WINDOW) -> Option<Input>
+{
+  match super::wget_wch(w)
+  {
+    Some(super::WchResult::Char(c)) =>
+      Some(Input::Char(::std::char::from_u32(c).unwrap_or(::std::char::REPLACEMENT_CHARACTER))),
+    Some(super::WchResult::KeyCode(k)) => Some(Input::Key(k)),
+    None => None,
+  }
+}
+
+
+/* Without the wide library, multi-byte characters arrive one byte per wgetch. */
+#[cfg(not(feature="wide"))]
+fn read_input(w: WINDOW) -> Option<Input>
+{
+  let first = unsafe { ll::wgetch(w) };
+  if first == ERR
+  { return None; }
+  if first > 0xff
+  { return Some(Input::Key(first)); }
+
+  let len = match first
+  {
+    0xc0..=0xdf => 2,
+    0xe0..=0xef => 3,
+    0xf0..=0xf7 => 4,
+    _ => 1,
+  };
+  let mut bytes = vec![first as u8];
+  while bytes.len() < len
+  {
+    let b = unsafe { ll::wgetch(w) };
+    if b == ERR || b > 0xff
+    { break; }
+    bytes.push(b as u8);
+  }
+
+  let c = ::std::str::from_utf8(&bytes).ok()
+    .and_then(|s| s.chars().next())
+    .unwrap_or(::std::char::REPLACEMENT_CHARACTER);
+  Some(Input::Char(c))
+}
+
+
+/// Reads a line from `w`, honouring the erase and kill characters.
+/// `max` limits the result to that many characters. Like `wgetnstr`,
+/// it reads in cbreak mode, then puts the modes back. Echoing is left
+/// to curses; whatever it showed for erased characters, or for the
+/// erase and kill keys themselves, is wiped again.
+pub fn read_line(call: &'static str, w: WINDOW, max: Option<usize>) -> CursesResult<String>
+{
+  in_cbreak(|| edit_line(w, max))
+    .ok_or_else(|| CursesError::new(call, ErrorKind::Failed))
+}
+
+
+/* ncurses 6.5 can say whether cbreak is already on. */
+#[cfg(feature="mode_queries")]
+fn in_cbreak<T, F: FnOnce() -> T>(f: F) -> T
+{
+  let was = unsafe { is_cbreak() } > 0;
+  if !was
+  { unsafe { ll::cbreak(); } }
+  let result = f();
+  if !was
+  { unsafe { ll::nocbreak(); } }
+  result
+}
+
+
+/* Before that, the tty modes are saved and put back around the read. */
+#[cfg(not(feature="mode_queries"))]
+fn in_cbreak<T, F: FnOnce() -> T>(f: F) -> T
+{
+  unsafe
+  {
+    ll::def_prog_mode();
+    ll::cbreak();
+  }
+  let result = f();
+  unsafe { ll::reset_prog_mode(); }
+  result
+}
+
+
+/* Clears what curses echoed since the cursor was at `at`, if anything. */
+fn unecho(w: WINDOW, at: (i32, i32))
+{
+  if (getcury(w), getcurx(w)) != at
+  {
+    wmove(w, at.0, at.1);
+    wclrtoeol(w);
+  }
+}
+
+
+fn edit_line(w: WINDOW, max: Option<usize>) -> Option<String>
+{
+  let erase = unsafe { ll::erasechar() } as u8 as char;
+  let kill = unsafe { ll::killchar() } as u8 as char;
+
+  /* Each character with the cursor position before it was read. */
+  let mut chars: Vec<(char, (i32, i32))> = Vec::new();
+
+  let rub_out = |chars: &mut Vec<(char, (i32, i32))>| -> bool
+  {
+    match chars.pop()
+    {
+      Some((_, at)) =>
+      {
+        unecho(w, at);
+        true
+      }
+      None => false,
+    }
+  };
+
+  loop
+  {
+    let at = (getcury(w), getcurx(w));
+    let input = read_input(w)?;
+    match input
+    {
+      Input::Char('\n') | Input::Char('\r') | Input::Key(KEY_ENTER) =>
+      {
+        /* Stay at the end of the line rather than below it. */
+        wmove(w, at.0, at.1);
+        break;
+      }
+      Input::Key(KEY_BACKSPACE) =>
+      { rub_out(&mut chars); }
+      Input::Char(c) if c == erase || c == '\x08' || c == '\x7f' =>
+      {
+        unecho(w, at);
+        rub_out(&mut chars);
+      }
+      Input::Char(c) if c == kill =>
+      {
+        unecho(w, at);
+        while rub_out(&mut chars) {}
+      }
+      /* Tabs and other control characters are kept too, echoed the way
+         curses shows them. */
+      Input::Char(c) =>
+      {
+        if max.is_some_and(|n| chars.len() >= n)
+        {
+          unecho(w, at);
+          beep();
+          continue;
+        }
+        chars.push((c, at));
+      }
+      Input::Key(_) => {}
+    }
+  }
+
+  Some(chars.into_iter().map(|(c, _)| c).collect())
+}
@@ -0,0 +1,33 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: macros.rs
+    Description:
+      printw-style macros taking Rust format
+      strings in place of C ones.
+*/
+
+/// Formats its arguments as `format!` does and prints them to stdscr.
+#[macro_export]
+macro_rules! printw(
+  ($($arg: tt)*) => ($crate::printw(&format!($($arg)*)));
+);
+
+/// `printw!` into a given window.
+#[macro_export]
+macro_rules! wprintw(
+  ($w: expr, $($arg: tt)*) => ($crate::wprintw($w, &format!($($arg)*)));
+);
+
+/// `printw!` at (y, x) on stdscr.
+#[macro_export]
+macro_rules! mvprintw(
+  ($y: expr, $x: expr, $($arg: tt)*) => ($crate::mvprintw($y, $x, &format!($($arg)*)));
+);
+
+/// `printw!` at (y, x) in a given window.
+#[macro_export]
+macro_rules! mvwprintw(
+  ($w: expr, $y: expr, $x: expr, $($arg: tt)*) => ($crate::mvwprintw($w, $y, $x, &format!($($arg)*)));
+);
@@ -14,6 +14,66 @@ pub type MENU = ll::MENU;
 pub type ITEM = ll::ITEM;
 pub type HOOK = ll::HOOK;
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MenuCode {
+    Ok,
+    SystemError,
+    BadArgument,
+    Posted,
+    Connected,
+    BadState,
+    NoRoom,
+    NotPosted,
+    UnknownCommand,
+    NoMatch,
+    NotSelectable,
+    NotConnected,
+    RequestDenied,
+    Unknown,
+}
+
+impl From<c_int> for MenuCode {
+    fn from(int: c_int) -> Self {
+        match int {
+            0 => MenuCode::Ok,
+            -1 => MenuCode::SystemError,
+            -2 => MenuCode::BadArgument,
+            -3 => MenuCode::Posted,
+            -4 => MenuCode::Connected,
+            -5 => MenuCode::BadState,
+            -6 => MenuCode::NoRoom,
+            -7 => MenuCode::NotPosted,
+            -8 => MenuCode::UnknownCommand,
+            -9 => MenuCode::NoMatch,
+            -10 => MenuCode::NotSelectable,
+            -11 => MenuCode::NotConnected,
+            -12 => MenuCode::RequestDenied,
+            _ => MenuCode::Unknown,
+        }
+    }
+}
+
+impl MenuCode {
+    pub fn description(&self) -> &'static str {
+        match *self {
+            MenuCode::Ok => "no error",
+            MenuCode::SystemError => "system error",
+            MenuCode::BadArgument => "bad argument",
+            MenuCode::Posted => "menu is already posted",
+            MenuCode::Connected => "item is already connected to a menu",
+            MenuCode::BadState => "called from an init or term hook",
+            MenuCode::NoRoom => "menu is too large for its window",
+            MenuCode::NotPosted => "menu has not been posted",
+            MenuCode::UnknownCommand => "unknown request",
+            MenuCode::NoMatch => "pattern did not match",
+            MenuCode::NotSelectable => "item cannot be selected",
+            MenuCode::NotConnected => "no items are connected to the menu",
+            MenuCode::RequestDenied => "request denied",
+            MenuCode::Unknown => "unknown error",
+        }
+    }
+}
+
 #[cfg(feature="menu")]
 pub fn menu_items(menu: MENU) -> Vec<ITEM> {
   unsafe {
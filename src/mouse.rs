@@ -0,0 +1,260 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: mouse.rs
+    Description:
+      Typed mouse events and masks over
+      getmouse, ungetmouse and mousemask.
+*/
+
+use std::ops::BitOr;
+use std::ptr;
+
+use ll::{self, MEVENT, mmask_t};
+use constants::*;
+
+/// A mouse button. With the version 2 mouse protocol buttons 4 and 5
+/// are the scroll wheel, reported as `MouseEventKind::WheelUp`/`WheelDown`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MouseButton
+{
+  Left,
+  Middle,
+  Right,
+  Button4,
+  Button5,
+}
+
+/// What happened to a button.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MouseAction
+{
+  Pressed,
+  Released,
+  Clicked,
+  DoubleClicked,
+  TripleClicked,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MouseEventKind
+{
+  Button(MouseButton, MouseAction),
+  WheelUp,
+  WheelDown,
+  /// The pointer moved; needs `REPORT_MOUSE_POSITION` in the mask.
+  Moved,
+  /// A state with no button bits set.
+  Unknown,
+}
+
+/// Keys held down during a mouse event.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Modifiers
+{
+  pub shift: bool,
+  pub ctrl: bool,
+  pub alt: bool,
+}
+
+/// A decoded `MEVENT`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MouseEvent
+{
+  pub id: i16,
+  pub x: i32,
+  pub y: i32,
+  pub z: i32,
+  pub kind: MouseEventKind,
+  pub modifiers: Modifiers,
+  /// The raw button state the event was decoded from.
+  pub bstate: mmask_t,
+}
+
+const BUTTONS: [MouseButton; 5] =
+[
+  MouseButton::Left,
+  MouseButton::Middle,
+  MouseButton::Right,
+  MouseButton::Button4,
+  MouseButton::Button5,
+];
+
+const ACTIONS: [(MouseAction, mmask_t); 5] =
+[
+  (MouseAction::Pressed, NCURSES_BUTTON_PRESSED),
+  (MouseAction::Released, NCURSES_BUTTON_RELEASED),
+  (MouseAction::Clicked, NCURSES_BUTTON_CLICKED),
+  (MouseAction::DoubleClicked, NCURSES_DOUBLE_CLICKED),
+  (MouseAction::TripleClicked, NCURSES_TRIPLE_CLICKED),
+];
+
+/* The event bits of `action` on button number `b` (1 to 5). With the
+   version 1 protocol there is no fifth button, and so no bits for it. */
+fn button_bits(b: usize, action: mmask_t) -> mmask_t
+{
+  if b == 5 && NCURSES_MOUSE_VERSION < 2
+  { 0 }
+  else
+  { ::ncurses_mouse_mask!(b, action) }
+}
+
+impl MouseButton
+{
+  fn number(self) -> usize
+  { BUTTONS.iter().position(|&b| b == self).unwrap() + 1 }
+}
+
+impl MouseAction
+{
+  fn bits(self) -> mmask_t
+  { ACTIONS.iter().find(|&&(a, _)| a == self).unwrap().1 }
+}
+
+impl MouseEvent
+{
+  pub(crate) fn from_raw(ev: &MEVENT) -> MouseEvent
+  {
+    let state = ev.bstate;
+    let mut kind = MouseEventKind::Unknown;
+
+    'found: for (i, &button) in BUTTONS.iter().enumerate()
+    {
+      for &(action, bits) in ACTIONS.iter()
+      {
+        if state & button_bits(i + 1, bits) != 0
+        {
+          kind = match (button, action)
+          {
+            (MouseButton::Button4, MouseAction::Pressed) => MouseEventKind::WheelUp,
+            (MouseButton::Button5, MouseAction::Pressed) => MouseEventKind::WheelDown,
+            _ => MouseEventKind::Button(button, action),
+          };
+          break 'found;
+        }
+      }
+    }
+    if kind == MouseEventKind::Unknown && state & REPORT_MOUSE_POSITION != 0
+    { kind = MouseEventKind::Moved; }
+
+    MouseEvent
+    {
+      id: ev.id,
+      x: ev.x,
+      y: ev.y,
+      z: ev.z,
+      kind,
+      modifiers: Modifiers
+      {
+        shift: state & BUTTON_SHIFT != 0,
+        ctrl: state & BUTTON_CTRL != 0,
+        alt: state & BUTTON_ALT != 0,
+      },
+      bstate: state,
+    }
+  }
+
+
+  pub(crate) fn to_raw(self) -> MEVENT
+  { MEVENT { id: self.id, x: self.x, y: self.y, z: self.z, bstate: self.bstate } }
+}
+
+/// Takes the pending mouse event after `getch` returned `KEY_MOUSE`.
+pub fn getmouse() -> Option<MouseEvent>
+{
+  let mut ev = MEVENT { id: 0, x: 0, y: 0, z: 0, bstate: 0 };
+  match unsafe { ll::getmouse(&mut ev) }
+  {
+    OK => Some(MouseEvent::from_raw(&ev)),
+    _ => None,
+  }
+}
+
+
+/// Queues `event` for the next `getmouse`, and `KEY_MOUSE` for `getch`.
+pub fn ungetmouse(event: &MouseEvent) -> i32
+{
+  let mut ev = event.to_raw();
+  unsafe { ll::ungetmouse(&mut ev) }
+}
+
+/// The events `mousemask` should report, built up from typed parts:
+///
+/// ```no_run
+/// # use ncurses::*;
+/// MouseMask::new()
+///   .button(MouseButton::Left, MouseAction::Clicked)
+///   .wheel()
+///   .apply();
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct MouseMask(mmask_t);
+
+impl MouseMask
+{
+  pub fn new() -> MouseMask
+  { MouseMask(0) }
+
+
+  /// Every button event, without position reports.
+  pub fn all() -> MouseMask
+  { MouseMask(ALL_MOUSE_EVENTS) }
+
+
+  pub fn button(self, button: MouseButton, action: MouseAction) -> MouseMask
+  { MouseMask(self.0 | button_bits(button.number(), action.bits())) }
+
+
+  /// `action` on every button.
+  pub fn action(self, action: MouseAction) -> MouseMask
+  {
+    BUTTONS.iter().fold(self, |m, &b| m.button(b, action))
+  }
+
+
+  pub fn wheel(self) -> MouseMask
+  {
+    self.button(MouseButton::Button4, MouseAction::Pressed)
+        .button(MouseButton::Button5, MouseAction::Pressed)
+  }
+
+
+  pub fn modifiers(self) -> MouseMask
+  { MouseMask(self.0 | BUTTON_SHIFT | BUTTON_CTRL | BUTTON_ALT) }
+
+
+  /// Report pointer motion as well as button events.
+  pub fn position(self) -> MouseMask
+  { MouseMask(self.0 | REPORT_MOUSE_POSITION) }
+
+
+  pub fn bits(self) -> mmask_t
+  { self.0 }
+
+
+  pub fn from_bits(bits: mmask_t) -> MouseMask
+  { MouseMask(bits) }
+
+
+  pub fn contains(self, other: MouseMask) -> bool
+  { self.0 & other.0 == other.0 }
+
+
+  /// Calls `mousemask`, returning the events the terminal will report.
+  pub fn apply(self) -> MouseMask
+  { MouseMask(unsafe { ll::mousemask(self.0, ptr::null_mut()) }) }
+}
+
+impl BitOr for MouseMask
+{
+  type Output = MouseMask;
+  fn bitor(self, rhs: MouseMask) -> MouseMask
+  { MouseMask(self.0 | rhs.0) }
+}
+
+impl From<MouseMask> for mmask_t
+{
+  fn from(m: MouseMask) -> mmask_t
+  { m.0 }
+}
@@ -24,19 +24,55 @@ pub use self::panel::wrapper::*;
 pub use self::form::wrapper::*;
 pub use self::menu::wrapper::*;
 pub use self::menu::constants::*;
+pub use self::window::Window;
+pub use self::attr::Attr;
+pub use self::key::{Key, ParseKeyError};
+pub use self::terminfo::{del_curterm, restartterm, set_curterm, setupterm, tigetflag, tigetnum, tigetstr, tparm, tputs, tputs_write, TParam, TermInfo, Terminal};
+pub use self::resize::{install_resize_handler, on_resize, term_size, term_size_of, update_term_size};
+pub use self::session::Session;
+pub use self::screen::Screen;
+pub use self::curses::Curses;
+pub use self::threaded::{use_screen, use_window};
+pub use self::mouse::{getmouse, ungetmouse, MouseAction, MouseButton, MouseEvent, MouseEventKind, MouseMask, Modifiers};
+pub use self::error::{CursesError, CursesResult, ErrorKind};
+#[cfg(feature="wide")]
+pub use self::wide::*;
+#[cfg(feature="wide")]
+pub use self::extcolor::*;
 
 pub type chtype = self::ll::chtype;
 pub type winttype = u32;
 
-pub type mmask_t = chtype;
+pub type mmask_t = self::ll::mmask_t;
 pub type attr_t = chtype;
 pub type NCURSES_ATTR_T = attr_t;
 
+#[macro_use]
+mod macros;
 pub mod ll;
 pub mod constants;
 pub mod panel;
 pub mod menu;
 pub mod form;
+pub mod window;
+pub mod attr;
+pub mod key;
+pub mod mouse;
+pub mod terminfo;
+pub mod resize;
+pub mod session;
+pub mod screen;
+pub mod curses;
+pub mod threaded;
+pub mod checked;
+#[cfg(feature="vt")]
+pub mod vt;
+mod error;
+mod line;
+#[cfg(feature="wide")]
+pub mod wide;
+#[cfg(feature="wide")]
+pub mod extcolor;
 
 trait FromCStr {
     fn from_c_str(s: *const libc::c_char) -> Self;
@@ -61,6 +97,9 @@ impl <'a>ToCStr for &'a str {
     }
 }
 
+/* Every printw call passes the text as an argument to this format. */
+const PRINTW_FMT: &[u8] = b"%s\0";
+
 #[derive(Clone, Copy)]
 pub enum CURSOR_VISIBILITY
 {
@@ -71,6 +110,7 @@ pub enum CURSOR_VISIBILITY
 
 pub type WINDOW = self::ll::WINDOW;
 pub type SCREEN = self::ll::SCREEN;
+pub type TERMINAL = self::ll::TERMINAL;
 pub type mmaskt = self::ll::mmask_t;
 pub type MEVENT = self::ll::MEVENT;
 
@@ -99,15 +139,15 @@ pub fn assume_default_colors(fg: i32, bg: i32) -> i32
 
 
 pub fn attroff(a: NCURSES_ATTR_T) -> i32
-{ unsafe { ll::attroff(a) } }
+{ unsafe { ll::attroff(a as i32) } }
 
 
 pub fn attron(a: NCURSES_ATTR_T) -> i32
-{ unsafe { ll::attron(a) } }
+{ unsafe { ll::attron(a as i32) } }
 
 
 pub fn attrset(a: NCURSES_ATTR_T) -> i32
-{ unsafe { ll::attrset(a) } }
+{ unsafe { ll::attrset(a as i32) } }
 
 
 pub fn attr_get(attrs: &mut attr_t, pair: &mut i16) -> i32
@@ -116,21 +156,21 @@ pub fn attr_get(attrs: &mut attr_t, pair: &mut i16) -> i32
   {
     ll::attr_get(&mut* attrs as *mut attr_t,
                  &mut* pair as *mut i16,
-                 ptr::null())
+                 ptr::null_mut())
   }
 }
 
 
 pub fn attr_off(a: attr_t) -> i32
-{ unsafe { ll::attr_off(a, ptr::null()) } }
+{ unsafe { ll::attr_off(a, ptr::null_mut()) } }
 
 
 pub fn attr_on(a: attr_t) -> i32
-{ unsafe { ll::attr_on(a, ptr::null()) } }
+{ unsafe { ll::attr_on(a, ptr::null_mut()) } }
 
 
 pub fn attr_set(attr: attr_t, pair: i16) -> i32
-{ unsafe { ll::attr_set(attr, pair, ptr::null()) } }
+{ unsafe { ll::attr_set(attr, pair, ptr::null_mut()) } }
 
 
 pub fn baudrate() -> i32
@@ -153,8 +193,8 @@ pub fn border(ls: chtype, rs: chtype, ts: chtype, bs: chtype, tl: chtype, tr: ch
 { unsafe { ll::border(ls, rs, ts, bs, tl, tr, bl, br) } }
 
 
-#[link_name="box"] pub fn box_(w: WINDOW, v: chtype, h: chtype) -> i32
-{ wborder(w, v, v, h, h, 0, 0, 0, 0) }
+pub fn box_(w: WINDOW, v: chtype, h: chtype) -> i32
+{ unsafe { ll::box_(w, v, h) } }
 
 
 pub fn can_change_color() -> bool
@@ -198,7 +238,7 @@ pub fn color_content(color: i16, r: &mut i16, g: &mut i16, b: &mut i16) -> i32
 
 
 pub fn color_set(pair: i16) -> i32
-{ unsafe { ll::color_set(pair, ptr::null()) } }
+{ unsafe { ll::color_set(pair, ptr::null_mut()) } }
 
 
 pub fn copywin(src_win: WINDOW, dest_win: WINDOW, src_min_row: i32,
@@ -299,6 +339,11 @@ pub fn flushinp() -> i32
 { unsafe { ll::flushinp() } }
 
 
+/// The `ESCDELAY` in use; it can't be assigned in the threaded build.
+pub fn get_escdelay() -> i32
+{ unsafe { ll::get_escdelay() } }
+
+
 pub fn getbkgd(w: WINDOW) -> chtype
 { unsafe { ll::getbkgd(w) } }
 
@@ -381,43 +426,18 @@ pub fn mvwget_wch(w: WINDOW, y: i32, x: i32) -> Option<WchResult> {
 
 pub fn unget_wch(ch: u32) -> i32 {
     unsafe {
-        ll::unget_wch(ch)
+        ll::unget_wch(ch as libc::wchar_t)
     }
 }
 
-pub fn getnstr(s: &mut String, n: i32) -> i32
-{
-  /* XXX: This is probably broken. */
-  unsafe
-  {
-    s.as_mut_vec().clear();
-    s.reserve(n as usize);
-    let buf = s.as_bytes().as_ptr();
-    let ret = ll::getnstr(mem::transmute(buf), n);
-
-    let capacity = s.capacity();
-    match s.find('\0')
-    {
-      Some(index) => s.as_mut_vec().set_len(index as usize),
-      None => s.as_mut_vec().set_len(capacity),
-    }
-
-    ret
-  }
-}
+/// Reads at most `n` characters from stdscr, up to a newline.
+/// The erase and kill characters edit the line as it is typed.
+pub fn getnstr(n: i32) -> CursesResult<String>
+{ wgetnstr(stdscr(), n) }
 
 
-pub fn getstr(s: &mut String) -> i32
-{
-  /* XXX: This is probably broken. */
-  let mut ch = getch();
-  while ch != '\n' as i32 && ch != '\r' as i32
-  {
-    unsafe { s.as_mut_vec().push(ch as u8); }
-    ch = getch();
-  }
-  OK
-}
+pub fn getstr() -> CursesResult<String>
+{ wgetstr(stdscr()) }
 
 
 pub fn getwin(reader: *mut libc::FILE) -> WINDOW
@@ -503,7 +523,7 @@ pub fn inchnstr(s: &mut Vec<chtype>, n: i32) -> i32
   s.reserve(n as usize);
   unsafe
   {
-    let ret = ll::inchnstr(s.as_ptr(), n);
+    let ret = ll::inchnstr(s.as_mut_ptr(), n);
 
     let capacity = s.capacity();
     match s.iter().position(|x| *x == 0)
@@ -522,7 +542,7 @@ pub fn inchstr(s: &mut Vec<chtype>) -> i32
   /* XXX: This is probably broken. */
   unsafe
   {
-    let ret = ll::inchstr(s.as_ptr());
+    let ret = ll::inchstr(s.as_mut_ptr());
 
     let capacity = s.capacity();
     match s.iter().position(|x| *x == 0)
@@ -678,6 +698,22 @@ pub fn is_syncok(w: WINDOW) -> bool
 { unsafe { ll::is_syncok(w) == TRUE }}
 
 
+/// The string the terminal sends for key code `keycode`; `count` picks
+/// among several bindings of one code. `None` once `count` runs out.
+pub fn keybound(keycode: i32, count: i32) -> Option<String>
+{
+  unsafe
+  {
+    let bound = ll::keybound(keycode, count);
+    if bound.is_null()
+    { return None; }
+    let s = CStr::from_ptr(bound).to_string_lossy().into_owned();
+    libc::free(bound as *mut libc::c_void);
+    Some(s)
+  }
+}
+
+
 pub fn keyname(c: i32) -> String
 { unsafe { FromCStr::from_c_str(ll::keyname(c)) } }
 
@@ -762,20 +798,12 @@ pub fn mvgetch(y: i32, x: i32) -> i32
 { unsafe { ll::mvgetch(y, x) } }
 
 
-pub fn mvgetnstr(y: i32, x: i32, s: &mut String, n: i32) -> i32
-{
-  if mv(y, x) == ERR
-  { return ERR; }
-  getnstr(s, n)
-}
+pub fn mvgetnstr(y: i32, x: i32, n: i32) -> CursesResult<String>
+{ mvwgetnstr(stdscr(), y, x, n) }
 
 
-pub fn mvgetstr(y: i32, x: i32, s: &mut String) -> i32
-{
-  if mv(y, x) == ERR
-  { return ERR; }
-  getstr(s)
-}
+pub fn mvgetstr(y: i32, x: i32) -> CursesResult<String>
+{ mvwgetstr(stdscr(), y, x) }
 
 
 pub fn mvhline(y: i32, x: i32, ch: chtype, n: i32) -> i32
@@ -839,11 +867,7 @@ pub fn mvinstr(y: i32, x: i32, s: &mut String) -> i32
 
 
 pub fn mvprintw(y: i32, x: i32, s: &str) -> i32
-{
-  if mv(y, x) == ERR
-  { return ERR; }
-  printw(s)
-}
+{ unsafe { ll::mvprintw(y, x, PRINTW_FMT.as_ptr() as ll::char_p, s.to_c_str().as_ptr()) } }
 
 
 pub fn mvvline(y: i32, x: i32, ch: chtype, n: i32) -> i32
@@ -882,41 +906,19 @@ pub fn mvwgetch(w: WINDOW, y: i32, x: i32) -> i32
 { unsafe { ll::mvwgetch(w, y, x) } }
 
 
-pub fn mvwgetnstr(w: WINDOW, y: i32, x: i32, s: &mut String, n: i32) -> i32
+pub fn mvwgetnstr(w: WINDOW, y: i32, x: i32, n: i32) -> CursesResult<String>
 {
-  /* XXX: This is probably broken. */
-  unsafe
-  {
-    s.as_mut_vec().clear();
-    s.reserve(n as usize);
-    let buf = s.as_bytes().as_ptr();
-    let ret = ll::mvwgetnstr(w, y, x, mem::transmute(buf), n);
-
-    let capacity = s.capacity();
-    match s.find('\0')
-    {
-      Some(index) => s.as_mut_vec().set_len(index as usize),
-      None => s.as_mut_vec().set_len(capacity),
-    }
-
-    ret
-  }
+  if wmove(w, y, x) == ERR
+  { return Err(CursesError::new("mvwgetnstr", ErrorKind::OutOfBounds)); }
+  line::read_line("mvwgetnstr", w, if n < 0 { None } else { Some(n as usize) })
 }
 
 
-pub fn mvwgetstr(w: WINDOW, y: i32, x: i32, s: &mut String) -> i32
+pub fn mvwgetstr(w: WINDOW, y: i32, x: i32) -> CursesResult<String>
 {
-  if mv(y, x) == ERR
-  { return ERR; }
-
-  /* XXX: This is probably broken. */
-  let mut ch = wgetch(w);
-  while ch != '\n' as i32 && ch != '\r' as i32
-  {
-    unsafe { s.as_mut_vec().push(ch as u8); }
-    ch = wgetch(w);
-  }
-  OK
+  if wmove(w, y, x) == ERR
+  { return Err(CursesError::new("mvwgetstr", ErrorKind::OutOfBounds)); }
+  line::read_line("mvwgetstr", w, None)
 }
 
 
@@ -939,7 +941,7 @@ pub fn mvwinchnstr(w: WINDOW, y: i32, x: i32, s: &mut Vec<chtype>, n: i32) -> i3
   s.reserve(n as usize);
   unsafe
   {
-    let ret = ll::mvwinchnstr(w, y, x, s.as_ptr(), n);
+    let ret = ll::mvwinchnstr(w, y, x, s.as_mut_ptr(), n);
 
     let capacity = s.capacity();
     match s.iter().position(|x| *x == 0)
@@ -958,7 +960,7 @@ pub fn mvwinchstr(w: WINDOW, y: i32, x: i32, s: &mut Vec<chtype>) -> i32
   /* XXX: This is probably broken. */
   unsafe
   {
-    let ret = ll::mvwinchstr(w, y, x, s.as_ptr());
+    let ret = ll::mvwinchstr(w, y, x, s.as_mut_ptr());
 
     let capacity = s.capacity();
     match s.iter().position(|x| *x == 0)
@@ -1027,7 +1029,7 @@ pub fn mvwinstr(w: WINDOW, y: i32, x: i32, s: &mut String) -> i32
 
 
 pub fn mvwprintw(w: WINDOW, y: i32, x: i32, s: &str) -> i32
-{ unsafe { ll::mvwprintw(w, y, x, s.to_c_str().as_ptr()) } }
+{ unsafe { ll::mvwprintw(w, y, x, PRINTW_FMT.as_ptr() as ll::char_p, s.to_c_str().as_ptr()) } }
 
 
 pub fn mvwvline(w: WINDOW, y: i32, x: i32, ch: chtype, n: i32) -> i32
@@ -1118,8 +1120,10 @@ pub fn prefresh(pad: WINDOW, pmin_row: i32, pmin_col: i32, smin_row: i32, smin_c
 { unsafe { ll::prefresh(pad, pmin_row, pmin_col, smin_row, smin_col, smax_row, smax_col) } }
 
 
+/// Prints `s` literally; a `%` in it is not a conversion.
+/// Use the `printw!` macro for formatted output.
 pub fn printw(s: &str) -> i32
-{ unsafe { ll::printw(s.to_c_str().as_ptr()) } }
+{ unsafe { ll::printw(PRINTW_FMT.as_ptr() as ll::char_p, s.to_c_str().as_ptr()) } }
 
 
 pub fn putp(s: &str) -> i32
@@ -1192,9 +1196,9 @@ pub fn scr_set(filename: &str) -> i32
 pub fn setlocale(lc: LcCategory, locale: &str) -> String
 {
   unsafe {
-    let buf = locale.to_c_str().as_ptr();
-    let ret = ll::setlocale(lc as libc::c_int, buf);
-    if ret == ptr::null() {
+    let buf = locale.to_c_str();
+    let ret = ll::setlocale(lc as libc::c_int, buf.as_ptr());
+    if ret.is_null() {
         String::new()
     } else {
         // The clone is necessary, as the returned pointer
@@ -1208,6 +1212,14 @@ pub fn setscrreg(top: i32, bot: i32) -> i32
 { unsafe { ll::setscrreg(top, bot) } }
 
 
+pub fn set_escdelay(ms: i32) -> i32
+{ unsafe { ll::set_escdelay(ms) } }
+
+
+pub fn set_tabsize(cols: i32) -> i32
+{ unsafe { ll::set_tabsize(cols) } }
+
+
 pub fn set_term(s: SCREEN) -> SCREEN
 { unsafe { ll::set_term(s) } }
 
@@ -1217,7 +1229,7 @@ pub fn slk_attroff(ch: chtype) -> i32
 
 //
 //pub fn slk_attr_off(ch: attr_t) -> i32
-//{ unsafe { ll::slk_attr_off(ch, ptr::null()) } }
+//{ unsafe { ll::slk_attr_off(ch, ptr::null_mut()) } }
 
 
 pub fn slk_attron(ch: chtype) -> i32
@@ -1225,7 +1237,7 @@ pub fn slk_attron(ch: chtype) -> i32
 
 //
 //pub fn slk_attr_on(ch: attr_t) -> i32
-//{ unsafe { ll::slk_attr_on(ch, ptr::null()) } }
+//{ unsafe { ll::slk_attr_on(ch, ptr::null_mut()) } }
 
 
 pub fn slk_attrset(ch: chtype) -> i32
@@ -1237,7 +1249,7 @@ pub fn slk_attr() -> attr_t
 
 
 pub fn slk_attr_set(attrs: attr_t, pair: i16) -> i32
-{ unsafe { ll::slk_attr_set(attrs, pair, ptr::null()) } }
+{ unsafe { ll::slk_attr_set(attrs, pair, ptr::null_mut()) } }
 
 
 pub fn slk_clear() -> i32
@@ -1324,22 +1336,6 @@ pub fn typeahead(fd: i32) -> i32
 { unsafe { ll::typeahead(fd) } }
 
 
-pub fn tigetflag(capname: &str) -> i32
-{ unsafe { ll::tigetflag(capname.to_c_str().as_ptr()) } }
-
-
-pub fn tigetnum(capname: &str) -> i32
-{ unsafe { ll::tigetnum(capname.to_c_str().as_ptr()) } }
-
-
-pub fn tigetstr(capname: &str) -> String
-{ unsafe { { FromCStr::from_c_str(ll::tigetstr(capname.to_c_str().as_ptr())) } } }
-
-
-pub fn tparm(s: &str) -> String
-{ unsafe { { FromCStr::from_c_str(ll::tparm(s.to_c_str().as_ptr())) } } }
-
-
 pub fn ungetch(ch: i32) -> i32
 { unsafe { ll::ungetch(ch) } }
 
@@ -1352,6 +1348,12 @@ pub fn use_env(f: bool)
 { unsafe { ll::use_env(f as ll::c_bool) } }
 
 
+/// With `true`, the size the terminal reports wins over `$LINES` and
+/// `$COLUMNS`; call before `initscr` or `newterm`.
+pub fn use_tioctl(f: bool)
+{ unsafe { ll::use_tioctl(f as ll::c_bool) } }
+
+
 pub fn use_default_colors() -> i32
 { unsafe { ll::use_default_colors() } }
 
@@ -1385,31 +1387,31 @@ pub fn waddstr(w: WINDOW, s: &str) -> i32
 
 
 pub fn wattron(w: WINDOW, attr: NCURSES_ATTR_T) -> i32
-{ unsafe { ll::wattron(w, attr) } }
+{ unsafe { ll::wattron(w, attr as i32) } }
 
 
 pub fn wattroff(w: WINDOW, attr: NCURSES_ATTR_T) -> i32
-{ unsafe { ll::wattroff(w, attr) } }
+{ unsafe { ll::wattroff(w, attr as i32) } }
 
 
 pub fn wattrset(w: WINDOW, attr: NCURSES_ATTR_T) -> i32
-{ unsafe { ll::wattrset(w, attr) } }
+{ unsafe { ll::wattrset(w, attr as i32) } }
 
 
 pub fn wattr_get(w: WINDOW, attrs: &mut attr_t, pair: &mut i16) -> i32
-{ unsafe { ll::wattr_get(w, &mut*attrs as *mut attr_t, &mut*pair as *mut i16, ptr::null()) } }
+{ unsafe { ll::wattr_get(w, &mut*attrs as *mut attr_t, &mut*pair as *mut i16, ptr::null_mut()) } }
 
 
 pub fn wattr_on(w: WINDOW, attr: attr_t) -> i32
-{ unsafe { ll::wattr_on(w, attr, ptr::null()) } }
+{ unsafe { ll::wattr_on(w, attr, ptr::null_mut()) } }
 
 
 pub fn wattr_off(w: WINDOW, attr: attr_t) -> i32
-{ unsafe { ll::wattr_off(w, attr, ptr::null()) } }
+{ unsafe { ll::wattr_off(w, attr, ptr::null_mut()) } }
 
 
 pub fn wattr_set(w: WINDOW, attrs: attr_t, pair: i16) -> i32
-{ unsafe { ll::wattr_set(w, attrs, pair, ptr::null()) } }
+{ unsafe { ll::wattr_set(w, attrs, pair, ptr::null_mut()) } }
 
 
 pub fn wbkgd(w: WINDOW, ch: chtype) -> i32
@@ -1441,7 +1443,7 @@ pub fn wclrtoeol(w: WINDOW) -> i32
 
 
 pub fn wcolor_set(w: WINDOW, pair: i16) -> i32
-{ unsafe { ll::wcolor_set(w, pair, ptr::null()) } }
+{ unsafe { ll::wcolor_set(w, pair, ptr::null_mut()) } }
 
 
 pub fn wcursyncup(w: WINDOW)
@@ -1468,37 +1470,13 @@ pub fn wgetch(w: WINDOW) -> i32
 { unsafe { ll::wgetch(w) } }
 
 
-pub fn wgetnstr(w: WINDOW, s: &mut String, n: i32) -> i32
-{
-  /* XXX: This is probably broken. */
-  unsafe
-  {
-    let buf = s.as_bytes().as_ptr();
-    let ret = ll::wgetnstr(w, mem::transmute(buf), n);
+/// Reads at most `n` characters from `w`; a negative `n` means no limit.
+pub fn wgetnstr(w: WINDOW, n: i32) -> CursesResult<String>
+{ line::read_line("wgetnstr", w, if n < 0 { None } else { Some(n as usize) }) }
 
-    let capacity = s.capacity();
-    match s.find('\0')
-    {
-      Some(index) => s.as_mut_vec().set_len(index as usize),
-      None => s.as_mut_vec().set_len(capacity),
-    }
 
-    ret
-  }
-}
-
-
-pub fn wgetstr(w: WINDOW, s: &mut String) -> i32
-{
-  /* XXX: This is probably broken. */
-  let mut ch = wgetch(w);
-  while ch != '\n' as i32 && ch != '\r' as i32
-  {
-    unsafe { s.as_mut_vec().push(ch as u8); }
-    ch = wgetch(w);
-  }
-  OK
-}
+pub fn wgetstr(w: WINDOW) -> CursesResult<String>
+{ line::read_line("wgetstr", w, None) }
 
 
 pub fn whline(w: WINDOW, ch: chtype, n: i32) -> i32
@@ -1516,7 +1494,7 @@ pub fn winchnstr(w: WINDOW, s: &mut Vec<chtype>, n: i32) -> i32
   s.reserve(n as usize);
   unsafe
   {
-    let ret = ll::winchnstr(w, s.as_ptr(), n);
+    let ret = ll::winchnstr(w, s.as_mut_ptr(), n);
 
     let capacity = s.capacity();
     match s.iter().position(|x| *x == 0)
@@ -1535,7 +1513,7 @@ pub fn winchstr(w: WINDOW, s: &mut Vec<chtype>) -> i32
   /* XXX: This is probably broken. */
   unsafe
   {
-    let ret = ll::winchstr(w, s.as_ptr());
+    let ret = ll::winchstr(w, s.as_mut_ptr());
 
     let capacity = s.capacity();
     match s.iter().position(|x| *x == 0)
@@ -1632,7 +1610,7 @@ pub fn wnoutrefresh(w: WINDOW) -> i32
 
 
 pub fn wprintw(w: WINDOW, s: &str) -> i32
-{ unsafe { ll::wprintw(w, s.to_c_str().as_ptr()) } }
+{ unsafe { ll::wprintw(w, PRINTW_FMT.as_ptr() as ll::char_p, s.to_c_str().as_ptr()) } }
 
 
 pub fn wredrawln(w: WINDOW, start: i32, n: i32) -> i32
@@ -1647,6 +1625,20 @@ pub fn wresize(w: WINDOW, lines: i32, cols: i32) -> i32
 { unsafe { ll::wresize(w, lines, cols) } }
 
 
+/// Resizes stdscr and curscr, and the windows ncurses can adjust with them.
+pub fn resizeterm(lines: i32, cols: i32) -> i32
+{ unsafe { ll::resizeterm(lines, cols) } }
+
+
+/// `resizeterm` without the bookkeeping for ripped-off lines and soft keys.
+pub fn resize_term(lines: i32, cols: i32) -> i32
+{ unsafe { ll::resize_term(lines, cols) } }
+
+
+pub fn is_term_resized(lines: i32, cols: i32) -> bool
+{ unsafe { ll::is_term_resized(lines, cols) == TRUE } }
+
+
 pub fn wscrl(w: WINDOW, n: i32) -> i32
 { unsafe { ll::wscrl(w, n) } }
 
@@ -1695,7 +1687,7 @@ pub fn NCURSES_BITS(mask: u32, shift: u32) -> u32
 { mask << (shift + NCURSES_ATTR_SHIFT) as usize }
 
 pub fn A_NORMAL() -> attr_t
-{ (1u32 - 1u32) as attr_t }
+{ Attr::NORMAL.bits() }
 
 pub fn A_ATTRIBUTES() -> attr_t
 { NCURSES_BITS(!(1u32 - 1u32), 0u32) as attr_t }
@@ -1707,49 +1699,52 @@ pub fn A_COLOR() -> attr_t
 { NCURSES_BITS(((1u32) << 8) - 1u32, 0u32) as attr_t }
 
 pub fn A_STANDOUT() -> attr_t
-{ NCURSES_BITS(1u32, 8u32) as attr_t }
+{ Attr::STANDOUT.bits() }
 
 pub fn A_UNDERLINE() -> attr_t
-{ NCURSES_BITS(1u32, 9u32) as attr_t }
+{ Attr::UNDERLINE.bits() }
 
 pub fn A_REVERSE() -> attr_t
-{ NCURSES_BITS(1u32, 10u32) as attr_t }
+{ Attr::REVERSE.bits() }
 
 pub fn A_BLINK() -> attr_t
-{ NCURSES_BITS(1u32, 11u32) as attr_t }
+{ Attr::BLINK.bits() }
 
 pub fn A_DIM() -> attr_t
-{ NCURSES_BITS(1u32, 12u32) as attr_t }
+{ Attr::DIM.bits() }
 
 pub fn A_BOLD() -> attr_t
-{ NCURSES_BITS(1u32, 13u32) as attr_t }
+{ Attr::BOLD.bits() }
 
 pub fn A_ALTCHARSET() -> attr_t
-{ NCURSES_BITS(1u32, 14u32) as attr_t }
+{ Attr::ALTCHARSET.bits() }
 
 pub fn A_INVIS() -> attr_t
-{ NCURSES_BITS(1u32, 15u32) as attr_t }
+{ Attr::INVIS.bits() }
 
 pub fn A_PROTECT() -> attr_t
-{ NCURSES_BITS(1u32, 16u32) as attr_t }
+{ Attr::PROTECT.bits() }
 
 pub fn A_HORIZONTAL() -> attr_t
-{ NCURSES_BITS(1u32, 17u32) as attr_t }
+{ Attr::HORIZONTAL.bits() }
 
 pub fn A_LEFT() -> attr_t
-{ NCURSES_BITS(1u32, 18u32) as attr_t }
+{ Attr::LEFT.bits() }
 
 pub fn A_LOW() -> attr_t
-{ NCURSES_BITS(1u32, 19u32) as attr_t }
+{ Attr::LOW.bits() }
 
 pub fn A_RIGHT() -> attr_t
-{ NCURSES_BITS(1u32, 20u32) as attr_t }
+{ Attr::RIGHT.bits() }
 
 pub fn A_TOP() -> attr_t
-{ NCURSES_BITS(1u32, 21u32) as attr_t }
+{ Attr::TOP.bits() }
 
 pub fn A_VERTICAL() -> attr_t
-{ NCURSES_BITS(1u32, 22u32) as attr_t }
+{ Attr::VERTICAL.bits() }
+
+pub fn A_ITALIC() -> attr_t
+{ Attr::ITALIC.bits() }
 
 /* Colors. */
 pub fn COLOR_PAIR(n: i16) -> attr_t
@@ -1974,12 +1969,6 @@ pub fn KEY_F(n: u8) -> i32
 pub fn has_mouse() -> bool
 { unsafe { ll::has_mouse() == TRUE } }
 
-pub fn getmouse(event: *mut MEVENT) -> i32
-{ unsafe { ll::getmouse(event) } }
-
-pub fn ungetmouse(event: *mut MEVENT) -> i32
-{ unsafe { ll::ungetmouse(event) } }
-
 pub fn mouseinterval(n: i32) -> i32
 { unsafe { ll::mouseinterval(n) } }
 
@@ -1992,7 +1981,7 @@ pub fn mousemask(newmask: mmask_t, oldmask: Option<&mut mmask_t>) -> mmask_t
 pub fn wenclose(w: WINDOW, y: i32, x: i32) -> bool
 { unsafe { ll::wenclose(w, y as libc::c_int, x as libc::c_int) == TRUE } }
 
-pub fn wmouse_trafo(w: *mut WINDOW, y: &mut[i32], x: &mut[i32], to_screen: bool) -> bool
+pub fn wmouse_trafo(w: WINDOW, y: &mut[i32], x: &mut[i32], to_screen: bool) -> bool
 { unsafe { ll::wmouse_trafo(w, y.as_mut_ptr(), x.as_mut_ptr(), to_screen as ll::c_bool) == TRUE } }
 
 pub fn mouse_trafo(y: &mut[i32], x: &mut[i32], to_screen: bool) -> bool
@@ -6,10 +6,10 @@ use ll::WINDOW;
 
 pub type PANEL = *mut i8;
 
-#[cfg(feature="panel")] #[link(name="panel")]
-extern {
+#[cfg(feature="panel")]
+extern "C" {
       pub fn panel_window(_:PANEL) -> WINDOW;
-      pub fn update_panels() -> c_void;
+      pub fn update_panels();
       pub fn hide_panel(_:PANEL) -> c_int;
       pub fn show_panel(_:PANEL) -> c_int;
       pub fn del_panel(_:PANEL) -> c_int;
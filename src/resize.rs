@@ -0,0 +1,155 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: resize.rs
+    Description:
+      Following terminal size changes: an opt-in
+      SIGWINCH handler, reflow callbacks and the
+      Key::Resize event.
+*/
+
+use std::cell::RefCell;
+use std::io;
+use std::mem;
+use std::os::unix::io::RawFd;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use libc::{self, c_int};
+use constants::*;
+use key::Key;
+use super::resizeterm;
+
+/* Set from the signal handler; everything else happens on the next read. */
+static PENDING: AtomicBool = AtomicBool::new(false);
+
+type Callback = Box<dyn FnMut(i32, i32)>;
+
+/* Callbacks capture windows, which can't leave their thread, so each
+   thread keeps its own list. */
+thread_local!(static CALLBACKS: RefCell<Vec<Callback>> = RefCell::new(Vec::new()));
+
+extern "C" fn on_sigwinch(_: c_int)
+{ PENDING.store(true, Ordering::SeqCst) }
+
+
+/// Replaces ncurses' SIGWINCH handling with one that only records the
+/// resize. The next `Window::get_key` then resizes the screen, runs the
+/// `on_resize` callbacks and returns `Key::Resize`.
+///
+/// The handler is installed without `SA_RESTART` so a blocked read
+/// returns as soon as the terminal changes size. The new size is read
+/// with `update_term_size`, so it follows the terminal on stdout or
+/// stdin; a `Screen` on another tty is resized with `Screen::update_size`.
+pub fn install_resize_handler() -> io::Result<()>
+{
+  unsafe
+  {
+    let mut action: libc::sigaction = mem::zeroed();
+    action.sa_sigaction = on_sigwinch as *const () as libc::sighandler_t;
+    action.sa_flags = 0;
+    libc::sigemptyset(&mut action.sa_mask);
+    if libc::sigaction(libc::SIGWINCH, &action, ptr::null_mut()) != 0
+    { return Err(io::Error::last_os_error()); }
+  }
+  Ok(())
+}
+
+
+/// Registers `f` to be called with the new `LINES` and `COLS` after each
+/// resize, to move and resize windows and panels. Panels are restacked
+/// after the callbacks run.
+///
+/// Callbacks belong to the registering thread and run only when that
+/// thread reads the resize. A callback may register further callbacks;
+/// they first run on the next resize.
+pub fn on_resize<F: FnMut(i32, i32) + 'static>(f: F)
+{ CALLBACKS.with(|c| c.borrow_mut().push(Box::new(f))) }
+
+
+/// The size of the controlling terminal as (lines, cols), from stdout or
+/// else stdin. Use `term_size_of` or `Screen::term_size` for any other
+/// terminal.
+pub fn term_size() -> Option<(i32, i32)>
+{
+  term_size_of(libc::STDOUT_FILENO).or_else(|| term_size_of(libc::STDIN_FILENO))
+}
+
+
+/// The size of the terminal behind `fd` as (lines, cols).
+pub fn term_size_of(fd: RawFd) -> Option<(i32, i32)>
+{
+  unsafe
+  {
+    let mut ws: libc::winsize = mem::zeroed();
+    if libc::ioctl(fd, libc::TIOCGWINSZ, &mut ws) == 0 && ws.ws_row > 0 && ws.ws_col > 0
+    { Some((i32::from(ws.ws_row), i32::from(ws.ws_col))) }
+    else
+    { None }
+  }
+}
+
+
+/// Reads the size of the terminal on stdout or stdin and, when it differs
+/// from `LINES` and `COLS`, calls `resizeterm` so both are brought up to
+/// date. Returns the size.
+pub fn update_term_size() -> Option<(i32, i32)>
+{
+  let (lines, cols) = term_size()?;
+  if lines != LINES() || cols != COLS()
+  { resizeterm(lines, cols); }
+  Some((lines, cols))
+}
+
+
+fn run_callbacks()
+{
+  let (lines, cols) = (LINES(), COLS());
+  /* The list is taken out while it runs so a callback can call on_resize. */
+  let mut running = CALLBACKS.with(|c| mem::take(&mut *c.borrow_mut()));
+  for f in running.iter_mut()
+  { f(lines, cols) }
+  CALLBACKS.with(|c|
+  {
+    let mut c = c.borrow_mut();
+    let added = mem::replace(&mut *c, running);
+    c.extend(added);
+  });
+  #[cfg(feature="panel")]
+  ::panel::wrapper::update_panels();
+}
+
+
+fn take_pending() -> bool
+{
+  if !PENDING.swap(false, Ordering::SeqCst)
+  { return false; }
+  update_term_size();
+  run_callbacks();
+  true
+}
+
+
+/* Wraps a key read so that pending or ncurses-reported resizes come back
+   as Key::Resize. A key read alongside a resize is returned first. A read
+   cut short by a suspend is retried once the screen is repainted. */
+pub fn read_key<F: FnMut() -> Option<Key>>(mut read: F) -> Option<Key>
+{
+  ::session::repaint_if_resumed();
+  if take_pending()
+  { return Some(Key::Resize); }
+
+  match read()
+  {
+    None if take_pending() => Some(Key::Resize),
+    None if ::session::repaint_if_resumed() => read_key(read),
+    Some(Key::Resize) =>
+    {
+      if !take_pending()
+      { run_callbacks(); }
+      Some(Key::Resize)
+    }
+    key => key,
+  }
+}
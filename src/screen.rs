@@ -0,0 +1,396 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: screen.rs
+    Description:
+      Screens created with newterm on any file
+      descriptor or tty path, freed on drop,
+      and driven through the *_sp functions so
+      no call depends on the current screen.
+*/
+
+use std::ffi::CString;
+use std::fs::OpenOptions;
+use std::io;
+use std::marker::PhantomData;
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::{AsRawFd, IntoRawFd, RawFd};
+use std::path::Path;
+use std::ptr;
+
+use libc::{self, c_char};
+use ll::{self, FILE_p, MEVENT, SCREEN, WINDOW};
+use constants::{OK, TRUE};
+use error::{CursesError, ErrorKind};
+use mouse::{MouseEvent, MouseMask};
+use window::Window;
+use super::CURSOR_VISIBILITY;
+
+/* A stdio stream on a duplicate of `fd`, so the caller keeps its own. */
+fn fdopen_dup(fd: RawFd, mode: &'static [u8]) -> io::Result<FILE_p>
+{
+  unsafe
+  {
+    let dup = libc::dup(fd);
+    if dup < 0
+    { return Err(io::Error::last_os_error()); }
+    let file = libc::fdopen(dup, mode.as_ptr() as *const c_char);
+    if file.is_null()
+    {
+      let err = io::Error::last_os_error();
+      libc::close(dup);
+      return Err(err);
+    }
+    Ok(file)
+  }
+}
+
+/// A curses screen on its own terminal, as made by `newterm`.
+///
+/// Creating a screen makes it the current one. Its methods and windows
+/// act on this screen whichever is current, so one thread can serve
+/// several terminals without `set_term`. Dropping it ends curses mode on
+/// that terminal, calls `delscreen` and closes its streams.
+///
+/// ```no_run
+/// # use ncurses::*;
+/// let other = Screen::open(Some("xterm"), "/dev/pts/3").unwrap();
+/// printw("on pts/3");
+/// refresh();
+/// # drop(other);
+/// ```
+pub struct Screen
+{
+  screen: SCREEN,
+  stdscr: WINDOW,
+  input: FILE_p,
+  output: FILE_p,
+  /* Curses calls are not thread-safe. */
+  _not_send: PhantomData<*mut ()>,
+}
+
+impl Screen
+{
+  /// Starts curses on the terminal behind `input` and `output`; `ty` is
+  /// the terminal type, `$TERM` when `None`. The descriptors are
+  /// duplicated, so the caller's handles may be closed independently.
+  pub fn new<I: AsRawFd, O: AsRawFd>(ty: Option<&str>, input: &I, output: &O) -> io::Result<Screen>
+  { Screen::from_fds(ty, input.as_raw_fd(), output.as_raw_fd()) }
+
+
+  /// Starts curses on the tty at `path`, such as `/dev/pts/3`.
+  pub fn open<P: AsRef<Path>>(ty: Option<&str>, path: P) -> io::Result<Screen>
+  {
+    let tty = OpenOptions::new().read(true)
+                                .write(true)
+                                .custom_flags(libc::O_NOCTTY)
+                                .open(path)?;
+    let fd = tty.into_raw_fd();
+    let screen = Screen::from_fds(ty, fd, fd);
+    unsafe { libc::close(fd); }
+    screen
+  }
+
+
+  fn from_fds(ty: Option<&str>, input: RawFd, output: RawFd) -> io::Result<Screen>
+  {
+    let ty = match ty
+    {
+      Some(t) => Some(CString::new(t).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?),
+      None => None,
+    };
+
+    let output = fdopen_dup(output, b"w\0")?;
+    let input = match fdopen_dup(input, b"r\0")
+    {
+      Ok(f) => f,
+      Err(e) =>
+      {
+        unsafe { libc::fclose(output); }
+        return Err(e);
+      }
+    };
+
+    /* newterm is new_prescr and newterm_sp plus forgetting the prescreen,
+       which is private; calling the two directly would hand the next
+       screen this one's SCREEN. */
+    let screen = unsafe { ll::newterm(ty.as_ref().map_or(ptr::null(), |t| t.as_ptr()), output, input) };
+    if screen.is_null()
+    {
+      unsafe
+      {
+        libc::fclose(input);
+        libc::fclose(output);
+      }
+      return Err(io::Error::other(CursesError::new("newterm", ErrorKind::Null)));
+    }
+
+    /* There is no stdscr_sp; newterm made this screen current, so the
+       global stdscr is its own. */
+    let stdscr = ::constants::stdscr();
+    Ok(Screen { screen, stdscr, input, output, _not_send: PhantomData })
+  }
+
+
+  pub fn as_raw(&self) -> SCREEN
+  { self.screen }
+
+
+  /// Makes this the screen curses calls act on, returning the previous one.
+  pub fn set_current(&self) -> SCREEN
+  { unsafe { ll::set_term(self.screen) } }
+
+
+  /// This screen's `stdscr`; reading keys from it reads this terminal.
+  pub fn stdscr(&self) -> Window<'_>
+  { Window::borrowed(self.stdscr) }
+
+
+  pub fn newwin(&self, lines: i32, cols: i32, begin_y: i32, begin_x: i32) -> Option<Window<'_>>
+  { Window::from_ptr(unsafe { ll::newwin_sp(self.screen, lines, cols, begin_y, begin_x) }) }
+
+
+  pub fn newpad(&self, lines: i32, cols: i32) -> Option<Window<'_>>
+  { Window::from_ptr(unsafe { ll::newpad_sp(self.screen, lines, cols) }) }
+
+
+  /// The size of this terminal as (lines, cols).
+  pub fn size(&self) -> (i32, i32)
+  { unsafe { (ll::getmaxy(self.stdscr), ll::getmaxx(self.stdscr)) } }
+
+
+  pub fn doupdate(&self) -> i32
+  { unsafe { ll::doupdate_sp(self.screen) } }
+
+
+  pub fn endwin(&self) -> i32
+  { unsafe { ll::endwin_sp(self.screen) } }
+
+
+  pub fn isendwin(&self) -> bool
+  { unsafe { ll::isendwin_sp(self.screen) == TRUE } }
+
+
+  pub fn cbreak(&self) -> i32
+  { unsafe { ll::cbreak_sp(self.screen) } }
+
+
+  pub fn nocbreak(&self) -> i32
+  { unsafe { ll::nocbreak_sp(self.screen) } }
+
+
+  pub fn raw(&self) -> i32
+  { unsafe { ll::raw_sp(self.screen) } }
+
+
+  pub fn noraw(&self) -> i32
+  { unsafe { ll::noraw_sp(self.screen) } }
+
+
+  pub fn echo(&self) -> i32
+  { unsafe { ll::echo_sp(self.screen) } }
+
+
+  pub fn noecho(&self) -> i32
+  { unsafe { ll::noecho_sp(self.screen) } }
+
+
+  pub fn nl(&self) -> i32
+  { unsafe { ll::nl_sp(self.screen) } }
+
+
+  pub fn nonl(&self) -> i32
+  { unsafe { ll::nonl_sp(self.screen) } }
+
+
+  pub fn halfdelay(&self, tenths: i32) -> i32
+  { unsafe { ll::halfdelay_sp(self.screen, tenths) } }
+
+
+  pub fn beep(&self) -> i32
+  { unsafe { ll::beep_sp(self.screen) } }
+
+
+  pub fn flash(&self) -> i32
+  { unsafe { ll::flash_sp(self.screen) } }
+
+
+  /// Returns the previous visibility, or `None` if the terminal can't.
+  pub fn curs_set(&self, visibility: CURSOR_VISIBILITY) -> Option<CURSOR_VISIBILITY>
+  {
+    match unsafe { ll::curs_set_sp(self.screen, visibility as i32) }
+    {
+      0 => Some(CURSOR_VISIBILITY::CURSOR_INVISIBLE),
+      1 => Some(CURSOR_VISIBILITY::CURSOR_VISIBLE),
+      2 => Some(CURSOR_VISIBILITY::CURSOR_VERY_VISIBLE),
+      _ => None,
+    }
+  }
+
+
+  pub fn napms(&self, ms: i32) -> i32
+  { unsafe { ll::napms_sp(self.screen, ms) } }
+
+
+  pub fn has_colors(&self) -> bool
+  { unsafe { ll::has_colors_sp(self.screen) == TRUE } }
+
+
+  pub fn start_color(&self) -> i32
+  { unsafe { ll::start_color_sp(self.screen) } }
+
+
+  pub fn init_pair(&self, pair: i16, f: i16, b: i16) -> i32
+  { unsafe { ll::init_pair_sp(self.screen, pair, f, b) } }
+
+
+  pub fn init_color(&self, color: i16, r: i16, g: i16, b: i16) -> i32
+  { unsafe { ll::init_color_sp(self.screen, color, r, g, b) } }
+
+
+  pub fn use_default_colors(&self) -> i32
+  { unsafe { ll::use_default_colors_sp(self.screen) } }
+
+
+  pub fn def_prog_mode(&self) -> i32
+  { unsafe { ll::def_prog_mode_sp(self.screen) } }
+
+
+  pub fn def_shell_mode(&self) -> i32
+  { unsafe { ll::def_shell_mode_sp(self.screen) } }
+
+
+  pub fn reset_prog_mode(&self) -> i32
+  { unsafe { ll::reset_prog_mode_sp(self.screen) } }
+
+
+  pub fn reset_shell_mode(&self) -> i32
+  { unsafe { ll::reset_shell_mode_sp(self.screen) } }
+
+
+  pub fn resizeterm(&self, lines: i32, cols: i32) -> i32
+  { unsafe { ll::resizeterm_sp(self.screen, lines, cols) } }
+
+
+  pub fn resize_term(&self, lines: i32, cols: i32) -> i32
+  { unsafe { ll::resize_term_sp(self.screen, lines, cols) } }
+
+
+  /// The size of the terminal behind this screen's output as (lines, cols).
+  pub fn term_size(&self) -> Option<(i32, i32)>
+  { ::resize::term_size_of(unsafe { libc::fileno(self.output) }) }
+
+
+  /// Reads this terminal's size and, when it differs from `size`, resizes
+  /// the screen to match. Returns the size.
+  pub fn update_size(&self) -> Option<(i32, i32)>
+  {
+    let (lines, cols) = self.term_size()?;
+    if (lines, cols) != self.size()
+    { self.resizeterm(lines, cols); }
+    Some((lines, cols))
+  }
+
+
+  pub fn is_term_resized(&self, lines: i32, cols: i32) -> bool
+  { unsafe { ll::is_term_resized_sp(self.screen, lines, cols) == TRUE } }
+
+
+  pub fn ungetch(&self, ch: i32) -> i32
+  { unsafe { ll::ungetch_sp(self.screen, ch) } }
+
+
+  pub fn flushinp(&self) -> i32
+  { unsafe { ll::flushinp_sp(self.screen) } }
+
+
+  pub fn typeahead(&self, fd: i32) -> i32
+  { unsafe { ll::typeahead_sp(self.screen, fd) } }
+
+
+  pub fn erasechar(&self) -> char
+  { unsafe { ll::erasechar_sp(self.screen) as u8 as char } }
+
+
+  pub fn killchar(&self) -> char
+  { unsafe { ll::killchar_sp(self.screen) as u8 as char } }
+
+
+  /// The string this terminal sends for `keycode`; see `keybound`.
+  pub fn keybound(&self, keycode: i32, count: i32) -> Option<String>
+  {
+    unsafe
+    {
+      let bound = ll::keybound_sp(self.screen, keycode, count);
+      if bound.is_null()
+      { return None; }
+      let s = ::std::ffi::CStr::from_ptr(bound).to_string_lossy().into_owned();
+      libc::free(bound as *mut libc::c_void);
+      Some(s)
+    }
+  }
+
+
+  pub fn keyname(&self, c: i32) -> Option<String>
+  {
+    unsafe
+    {
+      let name = ll::keyname_sp(self.screen, c);
+      if name.is_null()
+      { None }
+      else
+      { Some(::std::ffi::CStr::from_ptr(name).to_string_lossy().into_owned()) }
+    }
+  }
+
+
+  pub fn get_escdelay(&self) -> i32
+  { unsafe { ll::get_escdelay_sp(self.screen) } }
+
+
+  pub fn set_escdelay(&self, ms: i32) -> i32
+  { unsafe { ll::set_escdelay_sp(self.screen, ms) } }
+
+
+  /// `MouseMask::apply` for this screen.
+  pub fn mousemask(&self, mask: MouseMask) -> MouseMask
+  { MouseMask::from_bits(unsafe { ll::mousemask_sp(self.screen, mask.bits(), ptr::null_mut()) }) }
+
+
+  pub fn getmouse(&self) -> Option<MouseEvent>
+  {
+    let mut ev = MEVENT { id: 0, x: 0, y: 0, z: 0, bstate: 0 };
+    match unsafe { ll::getmouse_sp(self.screen, &mut ev) }
+    {
+      OK => Some(MouseEvent::from_raw(&ev)),
+      _ => None,
+    }
+  }
+
+
+  pub fn ungetmouse(&self, event: &MouseEvent) -> i32
+  {
+    let mut ev = event.to_raw();
+    unsafe { ll::ungetmouse_sp(self.screen, &mut ev) }
+  }
+}
+
+impl Drop for Screen
+{
+  fn drop(&mut self)
+  {
+    /* endwin_sp and delscreen still send some of the teardown through
+       cur_term, so the screen is made current first. */
+    unsafe
+    {
+      let previous = ll::set_term(self.screen);
+      ll::endwin_sp(self.screen);
+      ll::delscreen(self.screen);
+      if !previous.is_null() && previous != self.screen
+      { ll::set_term(previous); }
+      libc::fclose(self.input);
+      libc::fclose(self.output);
+    }
+  }
+}
@@ -0,0 +1,324 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: session.rs
+    Description:
+      A guard that gives the terminal back in
+      a usable state on drop, panic or signal.
+*/
+
+use std::io;
+use std::marker::PhantomData;
+use std::mem;
+use std::panic;
+use std::ptr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+use std::thread;
+
+use libc::{self, c_int};
+use ll;
+use constants::{self, TRUE};
+use terminfo::{tigetstr, tparm, tputs, TParam};
+use window::Window;
+
+/* At most one session may be live, since initscr can only run once. */
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/* Set while signal handlers are installed. */
+static SAVED: AtomicPtr<Saved> = AtomicPtr::new(ptr::null_mut());
+
+/* Set on resume from SIGTSTP; the repaint waits for the next key read. */
+static RESUMED: AtomicBool = AtomicBool::new(false);
+
+/* initscr drives the terminal on stdout. */
+const TTY: c_int = libc::STDOUT_FILENO;
+
+type PanicHook = Box<dyn Fn(&panic::PanicHookInfo) + Sync + Send + 'static>;
+
+/* Everything the signal handlers need, worked out beforehand so they only
+   make async-signal-safe calls: the bytes endwin would send, the bytes
+   that bring the screen back, and the shell's terminal modes. */
+struct Saved
+{
+  shell: Option<libc::termios>,
+  leave: Vec<u8>,
+  enter: Vec<u8>,
+}
+
+/* Leaves curses mode unless already out of it. Also used from the
+   panic hook. */
+fn restore()
+{
+  unsafe
+  {
+    if ll::isendwin() != TRUE
+    {
+      ll::endwin();
+      ll::reset_shell_mode();
+    }
+  }
+}
+
+
+/* Appends capability `cap`, padding expanded, to `out`. */
+fn put_cap(out: &mut Vec<u8>, cap: &str, params: &[TParam])
+{
+  if let Ok(Some(s)) = tigetstr(cap)
+  {
+    let s = if params.is_empty() { Some(s) } else { tparm(&s, params) };
+    if let Some(s) = s
+    { tputs(&s, 1, |b| out.push(b)); }
+  }
+}
+
+
+/* Writes `bytes` to the terminal; only async-signal-safe calls. */
+unsafe fn send(bytes: &[u8])
+{ libc::write(TTY, bytes.as_ptr() as *const libc::c_void, bytes.len()); }
+
+
+/* Puts the terminal back as the shell had it; only async-signal-safe
+   calls. */
+unsafe fn leave(saved: &Saved)
+{
+  send(&saved.leave);
+  if let Some(ref shell) = saved.shell
+  { libc::tcsetattr(TTY, libc::TCSADRAIN, shell); }
+}
+
+
+/* SIGTSTP is installed without SA_RESTART so that a read blocked when
+   the process was stopped returns, and the screen is repainted. */
+unsafe fn install(sig: c_int, handler: libc::sighandler_t, old: *mut libc::sigaction) -> c_int
+{
+  let mut action: libc::sigaction = mem::zeroed();
+  action.sa_sigaction = handler;
+  action.sa_flags = if sig == libc::SIGTSTP { 0 } else { libc::SA_RESTART };
+  libc::sigemptyset(&mut action.sa_mask);
+  libc::sigaction(sig, &action, old)
+}
+
+
+extern "C" fn on_terminate(sig: c_int)
+{
+  unsafe
+  {
+    let saved = SAVED.load(Ordering::SeqCst);
+    if !saved.is_null()
+    { leave(&*saved); }
+    libc::signal(sig, libc::SIG_DFL);
+    libc::raise(sig);
+  }
+}
+
+
+/* Suspends with the shell's modes, then puts the program's back on resume.
+   The signal is blocked while its handler runs, so it has to be unblocked
+   for the default action to stop the process. */
+extern "C" fn on_suspend(sig: c_int)
+{
+  unsafe
+  {
+    let saved = SAVED.load(Ordering::SeqCst);
+    let mut prog: libc::termios = mem::zeroed();
+    let have_prog = libc::tcgetattr(TTY, &mut prog) == 0;
+    if !saved.is_null()
+    { leave(&*saved); }
+
+    let mut mask: libc::sigset_t = mem::zeroed();
+    libc::sigemptyset(&mut mask);
+    libc::sigaddset(&mut mask, sig);
+
+    libc::signal(sig, libc::SIG_DFL);
+    libc::sigprocmask(libc::SIG_UNBLOCK, &mask, ptr::null_mut());
+    libc::raise(sig);
+
+    /* Execution continues here after SIGCONT. The shell may have changed
+       its modes while we were stopped. */
+    libc::sigprocmask(libc::SIG_BLOCK, &mask, ptr::null_mut());
+    install(sig, on_suspend as *const () as libc::sighandler_t, ptr::null_mut());
+    if !saved.is_null()
+    {
+      let mut shell: libc::termios = mem::zeroed();
+      if libc::tcgetattr(TTY, &mut shell) == 0
+      { (*saved).shell = Some(shell); }
+      if have_prog
+      { libc::tcsetattr(TTY, libc::TCSADRAIN, &prog); }
+      send(&(*saved).enter);
+    }
+    RESUMED.store(true, Ordering::SeqCst);
+  }
+}
+
+
+/* Repaints the whole screen if it was suspended since the last call, and
+   says whether it did. Input queued before the suspend is dropped, as
+   ncurses' own handler does; that includes the failed read it left. */
+pub(crate) fn repaint_if_resumed() -> bool
+{
+  if !RESUMED.swap(false, Ordering::SeqCst)
+  { return false; }
+  unsafe
+  {
+    ll::flushinp();
+    ll::wrefresh(constants::curscr());
+  }
+  true
+}
+
+const HANDLED: [c_int; 3] = [libc::SIGINT, libc::SIGTERM, libc::SIGTSTP];
+
+/// The curses screen, for as long as the value lives.
+///
+/// Dropping it, or unwinding past it, calls `endwin` and restores the
+/// shell's terminal modes. A panic restores the terminal before the panic
+/// message is printed, so the message stays readable.
+///
+/// ```no_run
+/// # use ncurses::*;
+/// let mut session = Session::init().expect("curses already running");
+/// session.handle_signals().unwrap();
+/// printw("hello");
+/// getch();
+/// ```
+pub struct Session
+{
+  previous_hook: Arc<PanicHook>,
+  previous_signals: Option<Vec<(c_int, libc::sigaction)>>,
+  shell: Option<libc::termios>,
+  /* Curses calls are not thread-safe. */
+  _not_send: PhantomData<*mut ()>,
+}
+
+impl Session
+{
+  /// Calls `initscr`, then saves the program's modes with `def_prog_mode`.
+  /// Returns `None` if a session is already running.
+  pub fn init() -> Option<Session>
+  {
+    if ACTIVE.swap(true, Ordering::SeqCst)
+    { return None; }
+
+    let previous_hook: Arc<PanicHook> = Arc::new(panic::take_hook());
+    let chained = previous_hook.clone();
+    panic::set_hook(Box::new(move |info| {
+      restore();
+      chained(info);
+    }));
+
+    let shell = unsafe
+    {
+      let mut shell: libc::termios = mem::zeroed();
+      if libc::tcgetattr(TTY, &mut shell) == 0 { Some(shell) } else { None }
+    };
+
+    ::initscr();
+    ::def_prog_mode();
+
+    Some(Session
+    {
+      previous_hook,
+      previous_signals: None,
+      shell,
+      _not_send: PhantomData,
+    })
+  }
+
+
+  /// Installs handlers so that SIGINT and SIGTERM restore the terminal
+  /// before the process dies, and SIGTSTP suspends with the shell's modes
+  /// and resumes with the program's. The previous handlers come back when
+  /// the session ends.
+  ///
+  /// Curses can't be called from a signal handler, so the handlers write
+  /// escape sequences worked out beforehand and set the terminal modes
+  /// directly. After a resume the screen is repainted by the next
+  /// `Window::get_key`.
+  pub fn handle_signals(&mut self) -> io::Result<()>
+  {
+    if self.previous_signals.is_some()
+    { return Ok(()); }
+
+    let mut saved = Saved { shell: self.shell, leave: Vec::new(), enter: Vec::new() };
+    put_cap(&mut saved.leave, "sgr0", &[]);
+    put_cap(&mut saved.leave, "cup", &[TParam::Num(constants::LINES() - 1), TParam::Num(0)]);
+    put_cap(&mut saved.leave, "cnorm", &[]);
+    put_cap(&mut saved.leave, "rmkx", &[]);
+    put_cap(&mut saved.leave, "rmcup", &[]);
+    put_cap(&mut saved.enter, "smcup", &[]);
+    put_cap(&mut saved.enter, "smkx", &[]);
+    SAVED.store(Box::into_raw(Box::new(saved)), Ordering::SeqCst);
+
+    let mut previous = Vec::new();
+    for &sig in HANDLED.iter()
+    {
+      let handler = if sig == libc::SIGTSTP
+      { on_suspend as *const () as libc::sighandler_t }
+      else
+      { on_terminate as *const () as libc::sighandler_t };
+
+      unsafe
+      {
+        let mut old: libc::sigaction = mem::zeroed();
+        if install(sig, handler, &mut old) != 0
+        {
+          let err = io::Error::last_os_error();
+          restore_signals(&previous);
+          forget_saved();
+          return Err(err);
+        }
+        previous.push((sig, old));
+      }
+    }
+    self.previous_signals = Some(previous);
+    Ok(())
+  }
+
+
+  pub fn stdscr(&self) -> Window<'static>
+  { Window::stdscr() }
+}
+
+fn restore_signals(previous: &[(c_int, libc::sigaction)])
+{
+  for &(sig, ref old) in previous.iter()
+  { unsafe { libc::sigaction(sig, old, ptr::null_mut()); } }
+}
+
+
+/* Frees what the handlers used, returning the shell's modes as last seen. */
+fn forget_saved() -> Option<libc::termios>
+{
+  let saved = SAVED.swap(ptr::null_mut(), Ordering::SeqCst);
+  if saved.is_null()
+  { None }
+  else
+  { unsafe { Box::from_raw(saved).shell } }
+}
+
+impl Drop for Session
+{
+  fn drop(&mut self)
+  {
+    restore();
+    if let Some(ref previous) = self.previous_signals
+    {
+      restore_signals(previous);
+      /* A resume may have found the shell with different modes than
+         ncurses saved at initscr. */
+      if let Some(shell) = forget_saved()
+      { unsafe { libc::tcsetattr(TTY, libc::TCSADRAIN, &shell); } }
+    }
+
+    /* The hook can't be swapped while unwinding; ours is harmless once
+       the terminal is restored. */
+    if !thread::panicking()
+    {
+      let previous_hook = self.previous_hook.clone();
+      panic::set_hook(Box::new(move |info| previous_hook(info)));
+    }
+    ACTIVE.store(false, Ordering::SeqCst);
+  }
+}
@@ -0,0 +1,348 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: terminfo.rs
+    Description:
+      Terminfo without a screen: setupterm and
+      the Terminal handle, capability lookups
+      that tell absent from mistyped, tparm
+      and tputs.
+*/
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::ffi::{CStr, CString};
+use std::io;
+use std::ptr;
+
+use libc::{c_char, c_int, c_long};
+use ll::{self, TERMINAL};
+use constants::{self, ERR, OK};
+use error::{CursesError, CursesResult, ErrorKind};
+
+/* Runs setupterm or restartterm, decoding its errret. */
+fn load_term(call: &'static str,
+             f: unsafe extern "C" fn(*const c_char, c_int, *mut c_int) -> c_int,
+             term: Option<&str>, fd: i32) -> CursesResult<()>
+{
+  let name = match term
+  {
+    Some(t) => Some(CString::new(t).map_err(|_| CursesError::new(call, ErrorKind::NoSuchTerminal))?),
+    None => None,
+  };
+  let mut errret: c_int = 0;
+  let rc = unsafe { f(name.as_ref().map_or(ptr::null(), |n| n.as_ptr()), fd, &mut errret) };
+  if rc != ERR
+  { return Ok(()); }
+
+  let kind = match errret
+  {
+    1 => ErrorKind::HardcopyTerminal,
+    -1 => ErrorKind::NoTerminfoDatabase,
+    _ => ErrorKind::NoSuchTerminal,
+  };
+  Err(CursesError::new(call, kind))
+}
+
+
+/// Loads the terminfo entry for `term` (or `$TERM` when `None`) as the
+/// current terminal, for output on `fd`, without initialising the screen.
+pub fn setupterm(term: Option<&str>, fd: i32) -> CursesResult<()>
+{ load_term("setupterm", ll::setupterm, term, fd) }
+
+
+/// Like `setupterm`, but keeps the current terminal's screen state.
+pub fn restartterm(term: Option<&str>, fd: i32) -> CursesResult<()>
+{ load_term("restartterm", ll::restartterm, term, fd) }
+
+
+pub fn set_curterm(term: TERMINAL) -> TERMINAL
+{ unsafe { ll::set_curterm(term) } }
+
+
+pub fn del_curterm(term: TERMINAL) -> i32
+{ unsafe { ll::del_curterm(term) } }
+
+/// A terminfo entry loaded by `setupterm`, freed with `del_curterm` on drop.
+///
+/// ```no_run
+/// # use ncurses::*;
+/// let term = Terminal::setup(None, 1).unwrap();
+/// if let Ok(Some(bold)) = tigetstr("bold") {
+///   tputs_write(&bold, 1, &mut std::io::stdout()).unwrap();
+/// }
+/// # drop(term);
+/// ```
+pub struct Terminal
+{
+  term: TERMINAL,
+}
+
+impl Terminal
+{
+  /// Calls `setupterm` and takes ownership of the terminal it loads,
+  /// which becomes the current one.
+  pub fn setup(term: Option<&str>, fd: i32) -> CursesResult<Terminal>
+  {
+    let previous = constants::cur_term();
+    setupterm(term, fd)?;
+    let loaded = constants::cur_term();
+    if loaded.is_null() || loaded == previous
+    { return Err(CursesError::new("setupterm", ErrorKind::Null)); }
+    Ok(Terminal { term: loaded })
+  }
+
+
+  pub fn as_raw(&self) -> TERMINAL
+  { self.term }
+
+
+  /// Makes this the terminal the terminfo calls read from.
+  pub fn set_current(&self) -> TERMINAL
+  { set_curterm(self.term) }
+
+
+  pub fn is_current(&self) -> bool
+  { constants::cur_term() == self.term }
+}
+
+impl Drop for Terminal
+{
+  /* del_curterm also clears cur_term when it points here. */
+  fn drop(&mut self)
+  { del_curterm(self.term); }
+}
+
+fn not_capability(call: &'static str) -> CursesError
+{ CursesError::new(call, ErrorKind::NotCapability) }
+
+
+/// Whether boolean capability `capname` is set. Absent or cancelled
+/// capabilities read as `false`; names that are not boolean capabilities
+/// are an error.
+pub fn tigetflag(capname: &str) -> CursesResult<bool>
+{
+  let name = CString::new(capname).map_err(|_| not_capability("tigetflag"))?;
+  match unsafe { ll::tigetflag(name.as_ptr()) }
+  {
+    -1 => Err(not_capability("tigetflag")),
+    v => Ok(v > 0),
+  }
+}
+
+
+/// The value of numeric capability `capname`, `None` if absent or
+/// cancelled.
+pub fn tigetnum(capname: &str) -> CursesResult<Option<i32>>
+{
+  let name = CString::new(capname).map_err(|_| not_capability("tigetnum"))?;
+  match unsafe { ll::tigetnum(name.as_ptr()) }
+  {
+    -2 => Err(not_capability("tigetnum")),
+    -1 => Ok(None),
+    v => Ok(Some(v)),
+  }
+}
+
+
+/// The raw bytes of string capability `capname`, `None` if absent or
+/// cancelled.
+pub fn tigetstr(capname: &str) -> CursesResult<Option<Vec<u8>>>
+{
+  let name = CString::new(capname).map_err(|_| not_capability("tigetstr"))?;
+  unsafe
+  {
+    let s = ll::tigetstr(name.as_ptr());
+    if s as isize == -1
+    { Err(not_capability("tigetstr")) }
+    else if s.is_null()
+    { Ok(None) }
+    else
+    { Ok(Some(CStr::from_ptr(s).to_bytes().to_vec())) }
+  }
+}
+
+/* Reads a null-terminated array of capability names. */
+fn cap_names(names: *const *const c_char) -> Vec<String>
+{
+  let mut out = Vec::new();
+  unsafe
+  {
+    let mut p = names;
+    while !(*p).is_null()
+    {
+      out.push(CStr::from_ptr(*p).to_string_lossy().into_owned());
+      p = p.offset(1);
+    }
+  }
+  out
+}
+
+/// Every capability of the current terminal, copied out at once.
+/// Absent and cancelled capabilities are left out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TermInfo
+{
+  pub bools: BTreeMap<String, bool>,
+  pub nums: BTreeMap<String, i32>,
+  pub strs: BTreeMap<String, Vec<u8>>,
+}
+
+impl TermInfo
+{
+  /// Snapshots the terminal set up by `initscr`, `newterm` or `setupterm`.
+  pub fn snapshot() -> TermInfo
+  {
+    let mut info = TermInfo::default();
+    for name in cap_names(constants::boolnames())
+    {
+      if let Ok(true) = tigetflag(&name)
+      { info.bools.insert(name, true); }
+    }
+    for name in cap_names(constants::numnames())
+    {
+      if let Ok(Some(n)) = tigetnum(&name)
+      { info.nums.insert(name, n); }
+    }
+    for name in cap_names(constants::strnames())
+    {
+      if let Ok(Some(s)) = tigetstr(&name)
+      { info.strs.insert(name, s); }
+    }
+    info
+  }
+
+
+  pub fn flag(&self, capname: &str) -> bool
+  { self.bools.get(capname).cloned().unwrap_or(false) }
+
+
+  pub fn num(&self, capname: &str) -> Option<i32>
+  { self.nums.get(capname).cloned() }
+
+
+  pub fn str(&self, capname: &str) -> Option<&[u8]>
+  { self.strs.get(capname).map(Vec::as_slice) }
+}
+
+/// A parameter to a terminfo capability string.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TParam<'a>
+{
+  Num(i32),
+  Str(&'a str),
+}
+
+impl<'a> From<i32> for TParam<'a>
+{
+  fn from(n: i32) -> TParam<'a>
+  { TParam::Num(n) }
+}
+
+impl<'a> From<&'a str> for TParam<'a>
+{
+  fn from(s: &'a str) -> TParam<'a>
+  { TParam::Str(s) }
+}
+
+/* tparm reads exactly this many parameters. */
+const TPARM_MAX: usize = 9;
+
+/* A TParam ready to be passed to tparm. */
+enum Arg
+{
+  Num(c_long),
+  Str(CString),
+}
+
+/// Instantiates capability `s` with `params`, as in
+/// `tparm(&cup, &[TParam::Num(y), TParam::Num(x)])`. The result is the
+/// raw escape sequence, which need not be UTF-8.
+///
+/// Returns `None` for more than nine parameters, a string containing
+/// NUL, or a capability ncurses can't expand.
+pub fn tparm(s: &[u8], params: &[TParam]) -> Option<Vec<u8>>
+{
+  if params.len() > TPARM_MAX
+  { return None; }
+
+  /* Every string is converted before any pointer to one is taken. */
+  let cap = CString::new(s).ok()?;
+  let owned = params.iter()
+                    .map(|p| match *p {
+                      TParam::Num(n) => Some(Arg::Num(c_long::from(n))),
+                      TParam::Str(s) => CString::new(s).ok().map(Arg::Str),
+                    })
+                    .collect::<Option<Vec<Arg>>>()?;
+
+  /* String parameters are passed as pointers in the long slots. */
+  let mut args: [c_long; TPARM_MAX] = [0; TPARM_MAX];
+  for (slot, arg) in args.iter_mut().zip(owned.iter())
+  {
+    *slot = match *arg
+    {
+      Arg::Num(n) => n,
+      Arg::Str(ref c) => c.as_ptr() as c_long,
+    };
+  }
+
+  unsafe
+  {
+    let out = ll::tparm(cap.as_ptr(),
+                        args[0], args[1], args[2], args[3], args[4],
+                        args[5], args[6], args[7], args[8]);
+    if out.is_null()
+    { None }
+    else
+    { Some(CStr::from_ptr(out).to_bytes().to_vec()) }
+  }
+}
+
+thread_local!(static TPUTS_OUT: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) });
+
+extern "C" fn tputs_collect(c: c_int) -> c_int
+{
+  TPUTS_OUT.with(|out| out.borrow_mut().push(c as u8));
+  c
+}
+
+/* Runs tputs, returning what it would have written. */
+fn tputs_bytes(s: &[u8], affcnt: i32) -> Option<Vec<u8>>
+{
+  let cap = CString::new(s).ok()?;
+  TPUTS_OUT.with(|out| out.borrow_mut().clear());
+  let rc = unsafe { ll::tputs(cap.as_ptr(), affcnt, Some(tputs_collect)) };
+  let bytes = TPUTS_OUT.with(|out| out.replace(Vec::new()));
+  if rc == ERR
+  { None }
+  else
+  { Some(bytes) }
+}
+
+
+/// Expands the padding in `s` for `affcnt` affected lines and passes
+/// each output byte to `putc`. Returns `ERR` if `s` contains NUL.
+pub fn tputs<F: FnMut(u8)>(s: &[u8], affcnt: i32, mut putc: F) -> i32
+{
+  match tputs_bytes(s, affcnt)
+  {
+    Some(bytes) =>
+    {
+      bytes.into_iter().for_each(&mut putc);
+      OK
+    }
+    None => ERR,
+  }
+}
+
+
+/// `tputs` into any writer.
+pub fn tputs_write<W: io::Write>(s: &[u8], affcnt: i32, w: &mut W) -> io::Result<()>
+{
+  match tputs_bytes(s, affcnt)
+  {
+    Some(bytes) => w.write_all(&bytes),
+    None => Err(io::Error::new(io::ErrorKind::InvalidInput, "tputs failed")),
+  }
+}
@@ -0,0 +1,78 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: threaded.rs
+    Description:
+      Closure wrappers over use_window and
+      use_screen, which serialise access in
+      the reentrant ncursest libraries.
+*/
+
+use std::any::Any;
+use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+
+use libc::{c_int, c_void};
+use ll::{self, SCREEN, WINDOW};
+use constants::OK;
+
+/* The closure going in, and its result or panic coming back out. */
+struct Call<T, F, R>
+{
+  f: Option<F>,
+  result: Option<Result<R, Box<dyn Any + Send>>>,
+  _arg: PhantomData<T>,
+}
+
+/* Runs the closure behind `data`; unwinding into C would abort, so a
+   panic is carried back and resumed once the library lock is released. */
+extern "C" fn trampoline<T, F: FnOnce(T) -> R, R>(arg: T, data: *mut c_void) -> c_int
+{
+  let call = unsafe { &mut *(data as *mut Call<T, F, R>) };
+  if let Some(f) = call.f.take()
+  { call.result = Some(panic::catch_unwind(AssertUnwindSafe(|| f(arg)))); }
+  OK
+}
+
+
+fn finish<R>(call: &str, result: Option<Result<R, Box<dyn Any + Send>>>) -> R
+{
+  match result
+  {
+    Some(Ok(r)) => r,
+    Some(Err(payload)) => panic::resume_unwind(payload),
+    None => panic!("{} did not run its callback", call),
+  }
+}
+
+
+/// Runs `f` on `w` while holding the library's lock, so other threads
+/// using `use_window` or `use_screen` can't touch curses meanwhile.
+/// Without the `threaded` feature there is no lock and `f` just runs.
+///
+/// ```no_run
+/// # use ncurses::*;
+/// let (y, x) = use_window(stdscr(), |w| {
+///   waddstr(w, "hi");
+///   (getcury(w), getcurx(w))
+/// });
+/// ```
+pub fn use_window<F, R>(w: WINDOW, f: F) -> R
+  where F: FnOnce(WINDOW) -> R
+{
+  let mut call: Call<WINDOW, F, R> = Call { f: Some(f), result: None, _arg: PhantomData };
+  unsafe { ll::use_window(w, trampoline::<WINDOW, F, R>, &mut call as *mut _ as *mut c_void); }
+  finish("use_window", call.result)
+}
+
+
+/// Runs `f` on the screen `s` while holding the library's lock; see
+/// `use_window`.
+pub fn use_screen<F, R>(s: SCREEN, f: F) -> R
+  where F: FnOnce(SCREEN) -> R
+{
+  let mut call: Call<SCREEN, F, R> = Call { f: Some(f), result: None, _arg: PhantomData };
+  unsafe { ll::use_screen(s, trampoline::<SCREEN, F, R>, &mut call as *mut _ as *mut c_void); }
+  finish("use_screen", call.result)
+}
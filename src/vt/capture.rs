@@ -0,0 +1,215 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: vt/capture.rs
+    Description:
+      Reads a window's cells back out of curses
+      into a Grid, resolving color pairs to the
+      colors they stand for.
+*/
+
+use std::collections::HashMap;
+
+use ll::{self, WINDOW};
+use attr::Attr;
+use constants::ERR;
+use super::emulator::dec_graphics;
+use super::grid::{Cell, Color, Grid};
+
+/* The colors of each pair seen so far, looked up once. */
+type Pairs = HashMap<i32, (Color, Color)>;
+
+impl Grid
+{
+  /// The contents of `w` and its cursor, as the window holds them; pass
+  /// `curscr()` for what curses last sent to the terminal. Pairs are
+  /// resolved through `pair_content`, and colors past the first 256
+  /// through `color_content`, or their `extended_` forms where ncurses
+  /// has extended colors. ACS characters come back as the Unicode
+  /// line drawing characters they stand for.
+  ///
+  /// ```no_run
+  /// # use ncurses::*;
+  /// # use ncurses::vt::Grid;
+  /// let grid = Grid::from_window(curscr());
+  /// print!("{}", grid.to_ansi());
+  /// ```
+  pub fn from_window(w: WINDOW) -> Grid
+  {
+    let (lines, cols) = unsafe { (ll::getmaxy(w), ll::getmaxx(w)) };
+    if lines <= 0 || cols <= 0
+    { return Grid::new(0, 0); }
+    let mut grid = Grid::new(lines as usize, cols as usize);
+
+    /* Reading a row moves the cursor, so put it back afterwards. */
+    let (cy, cx) = unsafe { (ll::getcury(w), ll::getcurx(w)) };
+    let mut pairs = Pairs::new();
+    for y in 0..grid.lines()
+    { read_row(w, y, &mut grid, &mut pairs); }
+    unsafe { ll::wmove(w, cy, cx) };
+    grid.set_cursor((cy.max(0) as usize).min(grid.lines() - 1), (cx.max(0) as usize).min(grid.cols() - 1));
+    grid
+  }
+}
+
+#[cfg(not(feature="wide"))]
+fn read_row(w: WINDOW, y: usize, grid: &mut Grid, pairs: &mut Pairs)
+{
+  use ll::chtype;
+
+  let cols = grid.cols();
+  let mut buf: Vec<chtype> = vec![0; cols + 1];
+  let n = unsafe { ll::mvwinchnstr(w, y as i32, 0, buf.as_mut_ptr(), cols as i32) };
+  if n == ERR
+  { return; }
+  for (x, &ch) in buf[..cols].iter().enumerate()
+  {
+    let (c, attr, pair) = Attr::split(ch);
+    /* Narrow curses keeps a byte per cell; read it as Latin-1. */
+    let c = c as u8 as char;
+    put(grid, y, x, c.to_string(), attr, i32::from(pair), 1, pairs);
+  }
+}
+
+
+#[cfg(feature="wide")]
+fn read_row(w: WINDOW, y: usize, grid: &mut Grid, pairs: &mut Pairs)
+{
+  use super::char_width;
+
+  let cols = grid.cols();
+  let cells = match ::wide::mvwin_wchnstr(w, y as i32, 0, cols as i32)
+  {
+    Some(cells) => cells,
+    None => return,
+  };
+  let mut x = 0;
+  for cell in &cells
+  {
+    if x >= cols
+    { break; }
+    /* A cell getcchar can't take apart stays blank. */
+    let (text, attrs, pair) = match ::wide::getcchar(cell)
+    {
+      Some(parts) => parts,
+      None =>
+      {
+        x += 1;
+        continue;
+      }
+    };
+    let width = text.chars().next().map_or(1, char_width).max(1);
+    put(grid, y, x, text, Attr::from_bits_truncate(attrs), cell_pair(cell, pair), width, pairs);
+    x += width;
+  }
+}
+
+
+/* The pair of `cell`, given what getcchar said. Its short can't hold
+   every extended pair, so the int ncurses itself reads is used where
+   there is one. */
+#[cfg(all(feature="wide", feature="ext_colors"))]
+fn cell_pair(cell: &ll::cchar_t, _: i16) -> i32
+{ cell.ext_color }
+
+
+#[cfg(all(feature="wide", not(feature="ext_colors")))]
+fn cell_pair(_: &ll::cchar_t, pair: i16) -> i32
+{ i32::from(pair) }
+
+
+/* Stores a cell `width` columns wide at (y, x). */
+#[allow(clippy::too_many_arguments)]
+fn put(grid: &mut Grid, y: usize, x: usize, mut text: String, mut attr: Attr, pair: i32, width: usize,
+       pairs: &mut Pairs)
+{
+  if attr.contains(Attr::ALTCHARSET)
+  {
+    text = text.chars().map(dec_graphics).collect();
+    attr.remove(Attr::ALTCHARSET);
+  }
+  let (fg, bg) = *pairs.entry(pair).or_insert_with(|| pair_colors(pair));
+  *grid.cell_mut(y, x) = Cell { text, attr, fg, bg };
+  if width == 2 && x + 1 < grid.cols()
+  { *grid.cell_mut(y, x + 1) = Cell { text: String::new(), attr, fg, bg }; }
+}
+
+
+/* The colors of `pair`; both default when colors aren't started. */
+fn pair_colors(pair: i32) -> (Color, Color)
+{
+  match pair_content(pair)
+  {
+    Some((f, b)) => (color(f), color(b)),
+    None => (Color::Default, Color::Default),
+  }
+}
+
+
+/* Palette entry `n`, or what color_content says for one past 255. */
+pub(crate) fn color(n: i32) -> Color
+{
+  if n < 0
+  { return Color::Default; }
+  if n < 256
+  { return Color::Indexed(n as u8); }
+  match color_content(n)
+  {
+    Some((r, g, b)) => Color::Rgb(scale(r), scale(g), scale(b)),
+    None => Color::Default,
+  }
+}
+
+
+/* The colors in `pair`, through extended_pair_content where pairs and
+   colors are ints. */
+#[cfg(all(feature="wide", feature="ext_colors"))]
+pub(crate) fn pair_content(pair: i32) -> Option<(i32, i32)>
+{
+  let (mut f, mut b) = (0, 0);
+  if ::extended_pair_content(pair, &mut f, &mut b) == ERR
+  { None }
+  else
+  { Some((f, b)) }
+}
+
+
+#[cfg(not(all(feature="wide", feature="ext_colors")))]
+pub(crate) fn pair_content(pair: i32) -> Option<(i32, i32)>
+{
+  let (mut f, mut b) = (0, 0);
+  if pair > i32::from(i16::MAX) || ::pair_content(pair as i16, &mut f, &mut b) == ERR
+  { None }
+  else
+  { Some((i32::from(f), i32::from(b))) }
+}
+
+
+/* The components of color `n`, through extended_color_content where
+   colors are ints. */
+#[cfg(all(feature="wide", feature="ext_colors"))]
+pub(crate) fn color_content(n: i32) -> Option<(i32, i32, i32)>
+{
+  let (mut r, mut g, mut b) = (0, 0, 0);
+  if ::extended_color_content(n, &mut r, &mut g, &mut b) == ERR
+  { None }
+  else
+  { Some((r, g, b)) }
+}
+
+
+#[cfg(not(all(feature="wide", feature="ext_colors")))]
+pub(crate) fn color_content(n: i32) -> Option<(i32, i32, i32)>
+{
+  let (mut r, mut g, mut b) = (0, 0, 0);
+  if n > i32::from(i16::MAX) || ::color_content(n as i16, &mut r, &mut g, &mut b) == ERR
+  { None }
+  else
+  { Some((i32::from(r), i32::from(g), i32::from(b))) }
+}
+
+
+/* Curses measures color components in 0..=1000. */
+pub(crate) fn scale(component: i32) -> u8
+{ (component.clamp(0, 1000) as u32 * 255 / 1000) as u8 }
@@ -0,0 +1,803 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: vt/emulator.rs
+    Description:
+      Applies the xterm control sequences that
+      curses emits to a Grid. Anything it does
+      not know is consumed and ignored.
+*/
+
+use attr::Attr;
+use super::char_width;
+use super::grid::{Cell, Color, Grid};
+
+#[derive(Copy, Clone, Default)]
+struct Pen
+{
+  attr: Attr,
+  fg: Color,
+  bg: Color,
+}
+
+/* What DECSC keeps for DECRC. */
+#[derive(Copy, Clone, Default)]
+struct Saved
+{
+  y: usize,
+  x: usize,
+  pen: Pen,
+  graphics: [bool; 2],
+  shift: usize,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum State
+{
+  Ground,
+  Escape,
+  EscapeIntermediate(u8),
+  Csi,
+  /* OSC, DCS, PM and APC bodies, skipped up to BEL or ST. */
+  String,
+  StringEscape,
+}
+
+/// A terminal emulator for the xterm family, enough to follow curses.
+///
+/// It understands cursor motion, erasing, insert and delete, scrolling
+/// regions, SGR attributes with 8, 16, 256 and direct colors, the DEC
+/// line drawing set, the alternate screen and UTF-8. Bytes it can't
+/// make sense of are dropped.
+///
+/// ```
+/// # use ncurses::vt::Emulator;
+/// let mut vt = Emulator::new(2, 10);
+/// vt.feed(b"\x1b[2;3Hhi\x1b[1mthere");
+/// assert_eq!(vt.grid().row_text(1), "  hithere");
+/// ```
+pub struct Emulator
+{
+  grid: Grid,
+  /* The main screen, while the alternate one is shown. */
+  main: Option<Grid>,
+  pen: Pen,
+  saved: Saved,
+  graphics: [bool; 2],
+  shift: usize,
+  top: usize,
+  bottom: usize,
+  wrap_pending: bool,
+  autowrap: bool,
+  insert: bool,
+  tabs: Vec<bool>,
+  last: Option<char>,
+  state: State,
+  private: Option<u8>,
+  params: Vec<u32>,
+  param: Option<u32>,
+  intermediate: Option<u8>,
+  utf8: Vec<u8>,
+}
+
+impl Emulator
+{
+  pub fn new(lines: usize, cols: usize) -> Emulator
+  {
+    assert!(lines > 0 && cols > 0, "a terminal needs at least one cell");
+    Emulator
+    {
+      grid: Grid::new(lines, cols),
+      main: None,
+      pen: Pen::default(),
+      saved: Saved::default(),
+      graphics: [false; 2],
+      shift: 0,
+      top: 0,
+      bottom: lines - 1,
+      wrap_pending: false,
+      autowrap: true,
+      insert: false,
+      tabs: default_tabs(cols),
+      last: None,
+      state: State::Ground,
+      private: None,
+      params: Vec::new(),
+      param: None,
+      intermediate: None,
+      utf8: Vec::new(),
+    }
+  }
+
+
+  /// What the terminal shows now.
+  pub fn grid(&self) -> &Grid
+  { &self.grid }
+
+
+  /// Changes the size, as a window resize would; the contents are kept
+  /// where they fit and the scrolling region is reset.
+  pub fn resize(&mut self, lines: usize, cols: usize)
+  {
+    assert!(lines > 0 && cols > 0, "a terminal needs at least one cell");
+    self.grid = self.grid.resized(lines, cols);
+    self.main = self.main.as_ref().map(|g| g.resized(lines, cols));
+    self.top = 0;
+    self.bottom = lines - 1;
+    self.tabs = default_tabs(cols);
+    self.wrap_pending = false;
+  }
+
+
+  /// Interprets `bytes` as terminal output. Sequences may be split
+  /// across calls.
+  pub fn feed(&mut self, bytes: &[u8])
+  {
+    for &b in bytes
+    { self.byte(b); }
+  }
+
+
+  fn byte(&mut self, b: u8)
+  {
+    match self.state
+    {
+      State::Ground => self.ground(b),
+      State::Escape => self.escape(b),
+      State::EscapeIntermediate(i) => self.escape_intermediate(i, b),
+      State::Csi => self.csi(b),
+      State::String =>
+        match b
+        {
+          0x07 => self.state = State::Ground,
+          0x1b => self.state = State::StringEscape,
+          _ => (),
+        },
+      State::StringEscape =>
+        self.state = if b == b'\\' { State::Ground } else { State::String },
+    }
+  }
+
+
+  fn ground(&mut self, b: u8)
+  {
+    if !self.utf8.is_empty()
+    {
+      if b & 0xc0 == 0x80
+      {
+        self.utf8.push(b);
+        if self.utf8.len() == utf8_len(self.utf8[0])
+        {
+          let c = ::std::str::from_utf8(&self.utf8).ok()
+            .and_then(|s| s.chars().next())
+            .unwrap_or('\u{fffd}');
+          self.utf8.clear();
+          self.print(c);
+        }
+        return;
+      }
+      self.utf8.clear();
+      self.print('\u{fffd}');
+    }
+    match b
+    {
+      0x1b => self.state = State::Escape,
+      0x00..=0x1f | 0x7f => self.control(b),
+      0x20..=0x7e => self.print(b as char),
+      _ =>
+        if utf8_len(b) > 1
+        { self.utf8.push(b); }
+        else
+        { self.print('\u{fffd}'); },
+    }
+  }
+
+
+  fn control(&mut self, b: u8)
+  {
+    match b
+    {
+      0x08 =>
+      {
+        self.wrap_pending = false;
+        let (y, x) = self.grid.cursor();
+        self.grid.set_cursor(y, x.saturating_sub(1));
+      }
+      0x09 => self.tab(1),
+      0x0a..=0x0c => self.index(),
+      0x0d => self.move_to_col(0),
+      0x0e => self.shift = 1,
+      0x0f => self.shift = 0,
+      _ => (),
+    }
+  }
+
+
+  fn escape(&mut self, b: u8)
+  {
+    self.state = State::Ground;
+    match b
+    {
+      b'[' =>
+      {
+        self.state = State::Csi;
+        self.private = None;
+        self.params.clear();
+        self.param = None;
+        self.intermediate = None;
+      }
+      b']' | b'P' | b'X' | b'^' | b'_' => self.state = State::String,
+      0x20..=0x2f => self.state = State::EscapeIntermediate(b),
+      b'7' => self.save_cursor(),
+      b'8' => self.restore_cursor(),
+      b'D' => self.index(),
+      b'E' =>
+      {
+        self.move_to_col(0);
+        self.index();
+      }
+      b'H' =>
+      {
+        let x = self.grid.cursor().1;
+        self.tabs[x] = true;
+      }
+      b'M' => self.reverse_index(),
+      b'c' => *self = Emulator::new(self.grid.lines(), self.grid.cols()),
+      0x1b => self.state = State::Escape,
+      _ => (),
+    }
+  }
+
+
+  fn escape_intermediate(&mut self, i: u8, b: u8)
+  {
+    if (0x20..=0x2f).contains(&b)
+    { return; }
+    self.state = State::Ground;
+    match i
+    {
+      b'(' => self.graphics[0] = b == b'0',
+      b')' => self.graphics[1] = b == b'0',
+      _ => (),
+    }
+  }
+
+
+  fn csi(&mut self, b: u8)
+  {
+    match b
+    {
+      b'0'..=b'9' =>
+        self.param = Some(self.param.unwrap_or(0).saturating_mul(10).saturating_add((b - b'0') as u32)),
+      b';' | b':' =>
+      {
+        self.params.push(self.param.unwrap_or(0));
+        self.param = None;
+      }
+      b'<'..=b'?' =>
+        if self.params.is_empty() && self.param.is_none()
+        { self.private = Some(b); },
+      0x20..=0x2f => self.intermediate = Some(b),
+      0x40..=0x7e =>
+      {
+        if self.param.is_some() || !self.params.is_empty()
+        { self.params.push(self.param.unwrap_or(0)); }
+        self.state = State::Ground;
+        self.dispatch(b);
+      }
+      0x1b => self.state = State::Escape,
+      0x00..=0x1f => self.control(b),
+      _ => self.state = State::Ground,
+    }
+  }
+
+
+  /* Parameter i, with 0 or a missing one read as `default`. */
+  fn arg(&self, i: usize, default: u32) -> usize
+  {
+    match self.params.get(i)
+    {
+      Some(&n) if n != 0 => n as usize,
+      _ => default as usize,
+    }
+  }
+
+
+  fn dispatch(&mut self, b: u8)
+  {
+    if self.intermediate.is_some()
+    { return; }
+    let (y, x) = self.grid.cursor();
+    let n = self.arg(0, 1);
+    match (self.private, b)
+    {
+      (None, b'@') => self.insert_blanks(n),
+      (None, b'A') => self.move_to(y.saturating_sub(n).max(if y >= self.top { self.top } else { 0 }), x),
+      (None, b'B') | (None, b'e') =>
+        self.move_to((y + n).min(if y <= self.bottom { self.bottom } else { self.grid.lines() - 1 }), x),
+      (None, b'C') | (None, b'a') => self.move_to(y, x + n),
+      (None, b'D') => self.move_to(y, x.saturating_sub(n)),
+      (None, b'E') => self.move_to(y + n, 0),
+      (None, b'F') => self.move_to(y.saturating_sub(n), 0),
+      (None, b'G') | (None, b'`') => self.move_to(y, n - 1),
+      (None, b'H') | (None, b'f') => self.move_to(n - 1, self.arg(1, 1) - 1),
+      (None, b'I') => self.tab(n),
+      (None, b'J') => self.erase_display(self.arg(0, 0)),
+      (None, b'K') => self.erase_line(self.arg(0, 0)),
+      (None, b'L') => self.insert_lines(n),
+      (None, b'M') => self.delete_lines(n),
+      (None, b'P') => self.delete_chars(n),
+      (None, b'S') => self.grid.scroll(self.top, self.bottom, n as isize, self.pen.bg),
+      (None, b'T') => self.grid.scroll(self.top, self.bottom, -(n as isize), self.pen.bg),
+      (None, b'X') => self.erase_chars(n),
+      (None, b'Z') => self.back_tab(n),
+      (None, b'b') =>
+        if let Some(c) = self.last
+        {
+          for _ in 0..n
+          { self.print_mapped(c); }
+        },
+      (None, b'd') => self.move_to(n - 1, x),
+      (None, b'g') =>
+        match self.arg(0, 0)
+        {
+          0 => self.tabs[x] = false,
+          3 => self.tabs.iter_mut().for_each(|t| *t = false),
+          _ => (),
+        },
+      (None, b'h') => self.set_modes(true),
+      (None, b'l') => self.set_modes(false),
+      (None, b'm') => self.sgr(),
+      (None, b'r') =>
+      {
+        let lines = self.grid.lines();
+        let top = self.arg(0, 1) - 1;
+        let bottom = self.arg(1, lines as u32).min(lines) - 1;
+        if top < bottom
+        {
+          self.top = top;
+          self.bottom = bottom;
+          self.move_to(0, 0);
+        }
+      }
+      (None, b's') => self.save_cursor(),
+      (None, b'u') => self.restore_cursor(),
+      (Some(b'?'), b'h') => self.set_private_modes(true),
+      (Some(b'?'), b'l') => self.set_private_modes(false),
+      _ => (),
+    }
+  }
+
+
+  fn set_modes(&mut self, on: bool)
+  {
+    if self.params.contains(&4)
+    { self.insert = on; }
+  }
+
+
+  fn set_private_modes(&mut self, on: bool)
+  {
+    for i in 0..self.params.len()
+    {
+      match self.params[i]
+      {
+        7 => self.autowrap = on,
+        25 => self.grid.set_cursor_visible(on),
+        47 | 1047 | 1049 => self.alternate_screen(on, self.params[i] == 1049),
+        1048 => if on { self.save_cursor() } else { self.restore_cursor() },
+        _ => (),
+      }
+    }
+  }
+
+
+  fn alternate_screen(&mut self, on: bool, save_cursor: bool)
+  {
+    if on == self.main.is_some()
+    { return; }
+    if on
+    {
+      if save_cursor
+      { self.save_cursor(); }
+      let mut alternate = Grid::new(self.grid.lines(), self.grid.cols());
+      let (y, x) = self.grid.cursor();
+      alternate.set_cursor(y, x);
+      alternate.set_cursor_visible(self.grid.cursor_visible());
+      self.main = Some(::std::mem::replace(&mut self.grid, alternate));
+    }
+    else if let Some(mut main) = self.main.take()
+    {
+      let (y, x) = self.grid.cursor();
+      main.set_cursor(y, x);
+      main.set_cursor_visible(self.grid.cursor_visible());
+      self.grid = main;
+      if save_cursor
+      { self.restore_cursor(); }
+    }
+  }
+
+
+  fn sgr(&mut self)
+  {
+    if self.params.is_empty()
+    { self.params.push(0); }
+    let mut i = 0;
+    while i < self.params.len()
+    {
+      let pen = &mut self.pen;
+      match self.params[i]
+      {
+        0 => *pen = Pen::default(),
+        1 => pen.attr.insert(Attr::BOLD),
+        2 => pen.attr.insert(Attr::DIM),
+        3 => pen.attr.insert(Attr::ITALIC),
+        4 => pen.attr.insert(Attr::UNDERLINE),
+        5 | 6 => pen.attr.insert(Attr::BLINK),
+        7 => pen.attr.insert(Attr::REVERSE),
+        8 => pen.attr.insert(Attr::INVIS),
+        22 => pen.attr.remove(Attr::BOLD | Attr::DIM),
+        23 => pen.attr.remove(Attr::ITALIC),
+        24 => pen.attr.remove(Attr::UNDERLINE),
+        25 => pen.attr.remove(Attr::BLINK),
+        27 => pen.attr.remove(Attr::REVERSE),
+        28 => pen.attr.remove(Attr::INVIS),
+        n @ 30..=37 => pen.fg = Color::Indexed((n - 30) as u8),
+        39 => pen.fg = Color::Default,
+        n @ 40..=47 => pen.bg = Color::Indexed((n - 40) as u8),
+        49 => pen.bg = Color::Default,
+        n @ 90..=97 => pen.fg = Color::Indexed((n - 90 + 8) as u8),
+        n @ 100..=107 => pen.bg = Color::Indexed((n - 100 + 8) as u8),
+        n @ 38 | n @ 48 =>
+        {
+          let (color, used) = extended_color(&self.params[i + 1..]);
+          i += used;
+          if let Some(color) = color
+          {
+            if n == 38
+            { pen.fg = color; }
+            else
+            { pen.bg = color; }
+          }
+        }
+        _ => (),
+      }
+      i += 1;
+    }
+  }
+
+
+  fn print(&mut self, c: char)
+  {
+    let c = if self.graphics[self.shift] { dec_graphics(c) } else { c };
+    self.print_mapped(c);
+  }
+
+
+  fn print_mapped(&mut self, c: char)
+  {
+    let cols = self.grid.cols();
+    let width = char_width(c);
+    if width == 0
+    {
+      let (y, x) = self.grid.cursor();
+      let x = if self.wrap_pending { x } else { x.saturating_sub(1) };
+      let x = if x > 0 && self.grid.cell(y, x).is_continuation() { x - 1 } else { x };
+      self.grid.cell_mut(y, x).text.push(c);
+      return;
+    }
+    if self.wrap_pending && self.autowrap
+    {
+      self.move_to_col(0);
+      self.index();
+    }
+    self.wrap_pending = false;
+    let (mut y, mut x) = self.grid.cursor();
+    if width > cols
+    { return; }
+    if x + width > cols
+    {
+      if self.autowrap
+      {
+        self.move_to_col(0);
+        self.index();
+        y = self.grid.cursor().0;
+        x = 0;
+      }
+      else
+      { x = cols - width; }
+    }
+    if self.insert
+    {
+      let row = &mut self.grid.row_mut(y)[x..];
+      row.rotate_right(width);
+      for cell in &mut row[..width]
+      { *cell = Cell::default(); }
+    }
+    self.split_wide(y, x);
+    self.split_wide(y, x + width - 1);
+    let pen = self.pen;
+    *self.grid.cell_mut(y, x) = Cell { text: c.to_string(), attr: pen.attr, fg: pen.fg, bg: pen.bg };
+    if width == 2
+    { *self.grid.cell_mut(y, x + 1) = Cell { text: String::new(), attr: pen.attr, fg: pen.fg, bg: pen.bg }; }
+    self.last = Some(c);
+    if x + width >= cols
+    {
+      self.grid.set_cursor(y, cols - 1);
+      self.wrap_pending = self.autowrap;
+    }
+    else
+    { self.grid.set_cursor(y, x + width); }
+  }
+
+
+  /* Blanks the other half of a double-width character about to lose
+     the cell at (y, x). */
+  fn split_wide(&mut self, y: usize, x: usize)
+  {
+    let cols = self.grid.cols();
+    if self.grid.cell(y, x).is_continuation()
+    {
+      if x > 0
+      { *self.grid.cell_mut(y, x - 1) = Cell::default(); }
+    }
+    else if x + 1 < cols && self.grid.cell(y, x + 1).is_continuation()
+    { *self.grid.cell_mut(y, x + 1) = Cell::default(); }
+  }
+
+
+  fn move_to(&mut self, y: usize, x: usize)
+  {
+    self.wrap_pending = false;
+    let y = y.min(self.grid.lines() - 1);
+    let x = x.min(self.grid.cols() - 1);
+    self.grid.set_cursor(y, x);
+  }
+
+
+  fn move_to_col(&mut self, x: usize)
+  {
+    let y = self.grid.cursor().0;
+    self.move_to(y, x);
+  }
+
+
+  fn index(&mut self)
+  {
+    self.wrap_pending = false;
+    let (y, x) = self.grid.cursor();
+    if y == self.bottom
+    { self.grid.scroll(self.top, self.bottom, 1, self.pen.bg); }
+    else if y + 1 < self.grid.lines()
+    { self.grid.set_cursor(y + 1, x); }
+  }
+
+
+  fn reverse_index(&mut self)
+  {
+    self.wrap_pending = false;
+    let (y, x) = self.grid.cursor();
+    if y == self.top
+    { self.grid.scroll(self.top, self.bottom, -1, self.pen.bg); }
+    else if y > 0
+    { self.grid.set_cursor(y - 1, x); }
+  }
+
+
+  fn tab(&mut self, n: usize)
+  {
+    let (y, mut x) = self.grid.cursor();
+    let last = self.grid.cols() - 1;
+    for _ in 0..n
+    {
+      while x < last
+      {
+        x += 1;
+        if self.tabs[x]
+        { break; }
+      }
+    }
+    self.move_to(y, x);
+  }
+
+
+  fn back_tab(&mut self, n: usize)
+  {
+    let (y, mut x) = self.grid.cursor();
+    for _ in 0..n
+    {
+      while x > 0
+      {
+        x -= 1;
+        if self.tabs[x]
+        { break; }
+      }
+    }
+    self.move_to(y, x);
+  }
+
+
+  fn save_cursor(&mut self)
+  {
+    let (y, x) = self.grid.cursor();
+    self.saved = Saved { y, x, pen: self.pen, graphics: self.graphics, shift: self.shift };
+  }
+
+
+  fn restore_cursor(&mut self)
+  {
+    let saved = self.saved;
+    self.pen = saved.pen;
+    self.graphics = saved.graphics;
+    self.shift = saved.shift;
+    self.move_to(saved.y, saved.x);
+  }
+
+
+  fn blank_range(&mut self, y: usize, from: usize, to: usize)
+  {
+    let bg = self.pen.bg;
+    for cell in &mut self.grid.row_mut(y)[from..to]
+    { *cell = Cell::blank(bg); }
+  }
+
+
+  fn erase_display(&mut self, mode: usize)
+  {
+    let (y, x) = self.grid.cursor();
+    let (lines, cols) = (self.grid.lines(), self.grid.cols());
+    self.wrap_pending = false;
+    match mode
+    {
+      0 =>
+      {
+        self.blank_range(y, x, cols);
+        for row in y + 1..lines
+        { self.blank_range(row, 0, cols); }
+      }
+      1 =>
+      {
+        for row in 0..y
+        { self.blank_range(row, 0, cols); }
+        self.blank_range(y, 0, x + 1);
+      }
+      2 | 3 =>
+        for row in 0..lines
+        { self.blank_range(row, 0, cols); },
+      _ => (),
+    }
+  }
+
+
+  fn erase_line(&mut self, mode: usize)
+  {
+    let (y, x) = self.grid.cursor();
+    let cols = self.grid.cols();
+    self.wrap_pending = false;
+    match mode
+    {
+      0 => self.blank_range(y, x, cols),
+      1 => self.blank_range(y, 0, x + 1),
+      2 => self.blank_range(y, 0, cols),
+      _ => (),
+    }
+  }
+
+
+  fn erase_chars(&mut self, n: usize)
+  {
+    let (y, x) = self.grid.cursor();
+    let to = (x + n).min(self.grid.cols());
+    self.wrap_pending = false;
+    self.blank_range(y, x, to);
+  }
+
+
+  fn insert_blanks(&mut self, n: usize)
+  {
+    let (y, x) = self.grid.cursor();
+    let n = n.min(self.grid.cols() - x);
+    self.wrap_pending = false;
+    self.grid.row_mut(y)[x..].rotate_right(n);
+    self.blank_range(y, x, x + n);
+  }
+
+
+  fn delete_chars(&mut self, n: usize)
+  {
+    let (y, x) = self.grid.cursor();
+    let cols = self.grid.cols();
+    let n = n.min(cols - x);
+    self.wrap_pending = false;
+    self.grid.row_mut(y)[x..].rotate_left(n);
+    self.blank_range(y, cols - n, cols);
+  }
+
+
+  fn insert_lines(&mut self, n: usize)
+  {
+    let y = self.grid.cursor().0;
+    if y < self.top || y > self.bottom
+    { return; }
+    self.grid.scroll(y, self.bottom, -(n as isize), self.pen.bg);
+    self.move_to_col(0);
+  }
+
+
+  fn delete_lines(&mut self, n: usize)
+  {
+    let y = self.grid.cursor().0;
+    if y < self.top || y > self.bottom
+    { return; }
+    self.grid.scroll(y, self.bottom, n as isize, self.pen.bg);
+    self.move_to_col(0);
+  }
+}
+
+fn default_tabs(cols: usize) -> Vec<bool>
+{ (0..cols).map(|x| x > 0 && x % 8 == 0).collect() }
+
+
+fn utf8_len(lead: u8) -> usize
+{
+  match lead
+  {
+    0xc2..=0xdf => 2,
+    0xe0..=0xef => 3,
+    0xf0..=0xf4 => 4,
+    _ => 1,
+  }
+}
+
+
+/* The color after a 38 or 48, and how many parameters it took. */
+fn extended_color(params: &[u32]) -> (Option<Color>, usize)
+{
+  match params.first()
+  {
+    Some(&5) =>
+      (params.get(1).map(|&n| Color::Indexed(n.min(255) as u8)), 2),
+    Some(&2) if params.len() >= 4 =>
+      (Some(Color::Rgb(params[1].min(255) as u8, params[2].min(255) as u8, params[3].min(255) as u8)), 4),
+    _ => (None, params.len()),
+  }
+}
+
+
+/* The DEC special graphics set, selected with ESC ( 0; curses stores the
+   same letters for ACS characters. */
+pub(crate) fn dec_graphics(c: char) -> char
+{
+  match c
+  {
+    '`' => '◆',
+    'a' => '▒',
+    'f' => '°',
+    'g' => '±',
+    'h' => '␤',
+    'i' => '␋',
+    'j' => '┘',
+    'k' => '┐',
+    'l' => '┌',
+    'm' => '└',
+    'n' => '┼',
+    'o' => '⎺',
+    'p' => '⎻',
+    'q' => '─',
+    'r' => '⎼',
+    's' => '⎽',
+    't' => '├',
+    'u' => '┤',
+    'v' => '┴',
+    'w' => '┬',
+    'x' => '│',
+    'y' => '≤',
+    'z' => '≥',
+    '{' => 'π',
+    '|' => '≠',
+    '}' => '£',
+    '~' => '·',
+    _ => c,
+  }
+}
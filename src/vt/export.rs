@@ -0,0 +1,264 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: vt/export.rs
+    Description:
+      Renders a Grid as SGR-escaped text for a
+      terminal or as a standalone HTML <pre>,
+      for bug reports and screenshots.
+*/
+
+use attr::Attr;
+use super::capture::{color, color_content, pair_content, scale};
+use super::grid::{Cell, Color, Grid};
+
+pub type Rgb = (u8, u8, u8);
+
+/// The RGB values `to_html` gives palette colors and the defaults.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Palette
+{
+  colors: [Rgb; 256],
+  /// What `Color::Default` is in the foreground.
+  pub fg: Rgb,
+  /// What `Color::Default` is in the background.
+  pub bg: Rgb,
+}
+
+impl Palette
+{
+  /// xterm's stock colors: 16 system colors, the 6×6×6 cube and the
+  /// gray ramp, light gray on black.
+  pub fn xterm() -> Palette
+  {
+    const SYSTEM: [Rgb; 16] =
+    [
+      (0x00, 0x00, 0x00), (0xcd, 0x00, 0x00), (0x00, 0xcd, 0x00), (0xcd, 0xcd, 0x00),
+      (0x00, 0x00, 0xee), (0xcd, 0x00, 0xcd), (0x00, 0xcd, 0xcd), (0xe5, 0xe5, 0xe5),
+      (0x7f, 0x7f, 0x7f), (0xff, 0x00, 0x00), (0x00, 0xff, 0x00), (0xff, 0xff, 0x00),
+      (0x5c, 0x5c, 0xff), (0xff, 0x00, 0xff), (0x00, 0xff, 0xff), (0xff, 0xff, 0xff),
+    ];
+    let level = |n: usize| if n == 0 { 0 } else { (55 + n * 40) as u8 };
+    let mut colors = [(0, 0, 0); 256];
+    colors[..16].copy_from_slice(&SYSTEM);
+    for n in 0..216
+    { colors[16 + n] = (level(n / 36), level(n / 6 % 6), level(n % 6)); }
+    for n in 0..24
+    {
+      let gray = (8 + n * 10) as u8;
+      colors[232 + n] = (gray, gray, gray);
+    }
+    Palette { colors, fg: SYSTEM[7], bg: SYSTEM[0] }
+  }
+
+
+  /// The colors curses has for the current screen, read with
+  /// `color_content`. Curses only knows the real values of colors the
+  /// program set with `init_color`; unless `can_change_color` is true,
+  /// colors past the first eight keep their xterm values.
+  pub fn from_curses() -> Palette
+  {
+    let mut palette = Palette::xterm();
+    let count = if ::can_change_color() { 256 } else { 8 };
+    for n in 0..count.min(::COLORS().max(0) as usize)
+    {
+      if let Some((r, g, b)) = color_content(n as i32)
+      { palette.colors[n] = (scale(r), scale(g), scale(b)); }
+    }
+    if let Some((f, b)) = pair_content(0)
+    {
+      palette.fg = palette.resolve(color(f), palette.fg);
+      palette.bg = palette.resolve(color(b), palette.bg);
+    }
+    palette
+  }
+
+
+  /// The RGB value of palette entry `n`.
+  pub fn get(&self, n: u8) -> Rgb
+  { self.colors[n as usize] }
+
+
+  pub fn set(&mut self, n: u8, rgb: Rgb)
+  { self.colors[n as usize] = rgb; }
+
+
+  /// `color` as RGB, with `default` standing in for `Color::Default`.
+  pub fn resolve(&self, color: Color, default: Rgb) -> Rgb
+  {
+    match color
+    {
+      Color::Default => default,
+      Color::Indexed(n) => self.get(n),
+      Color::Rgb(r, g, b) => (r, g, b),
+    }
+  }
+}
+
+impl Default for Palette
+{
+  fn default() -> Palette
+  { Palette::xterm() }
+}
+
+impl Grid
+{
+  /// Each row with SGR escapes for its attributes and colors, for
+  /// showing on a terminal or pasting into a bug report. Trailing blank
+  /// cells are dropped and each styled row ends with a reset.
+  pub fn to_ansi(&self) -> String
+  {
+    let mut out = String::new();
+    for y in 0..self.lines()
+    {
+      let mut current = Style::default();
+      for cell in trimmed(self.row(y))
+      {
+        let style = Style::of(cell);
+        if style != current
+        {
+          out.push_str(&style.sgr());
+          current = style;
+        }
+        out.push_str(&cell.text);
+      }
+      if current != Style::default()
+      { out.push_str("\x1b[0m"); }
+      out.push('\n');
+    }
+    out
+  }
+
+
+  /// A standalone HTML `<pre>` showing the grid in `palette`'s colors,
+  /// with inline styles only so it can be dropped into any page.
+  pub fn to_html(&self, palette: &Palette) -> String
+  {
+    let mut out = format!("<pre style=\"font-family: monospace; color: {}; background-color: {}\">",
+                          hex(palette.fg), hex(palette.bg));
+    for y in 0..self.lines()
+    {
+      let mut current: Option<Style> = None;
+      for cell in trimmed(self.row(y))
+      {
+        let style = Style::of(cell);
+        if current != Some(style)
+        {
+          if current.is_some_and(|s| s != Style::default())
+          { out.push_str("</span>"); }
+          if style != Style::default()
+          { out.push_str(&format!("<span style=\"{}\">", style.css(palette))); }
+          current = Some(style);
+        }
+        escape_html(&mut out, &cell.text);
+      }
+      if current.is_some_and(|s| s != Style::default())
+      { out.push_str("</span>"); }
+      if y + 1 < self.lines()
+      { out.push('\n'); }
+    }
+    out.push_str("</pre>\n");
+    out
+  }
+}
+
+#[derive(Copy, Clone, Default, PartialEq)]
+struct Style
+{
+  attr: Attr,
+  fg: Color,
+  bg: Color,
+}
+
+impl Style
+{
+  fn of(cell: &Cell) -> Style
+  { Style { attr: cell.attr, fg: cell.fg, bg: cell.bg } }
+
+
+  /* A full reset followed by this style. */
+  fn sgr(&self) -> String
+  {
+    let mut codes = vec!["0".to_string()];
+    for &(attr, code) in &[(Attr::BOLD, "1"), (Attr::DIM, "2"), (Attr::ITALIC, "3"),
+                           (Attr::UNDERLINE, "4"), (Attr::BLINK, "5"), (Attr::REVERSE, "7"),
+                           (Attr::STANDOUT, "7"), (Attr::INVIS, "8")]
+    {
+      if self.attr.contains(attr) && !codes.iter().any(|c| c == code)
+      { codes.push(code.to_string()); }
+    }
+    if let Some(fg) = sgr_color(self.fg, 30, 90, 38)
+    { codes.push(fg); }
+    if let Some(bg) = sgr_color(self.bg, 40, 100, 48)
+    { codes.push(bg); }
+    format!("\x1b[{}m", codes.join(";"))
+  }
+
+
+  fn css(&self, palette: &Palette) -> String
+  {
+    let mut fg = palette.resolve(self.fg, palette.fg);
+    let mut bg = palette.resolve(self.bg, palette.bg);
+    if self.attr.intersects(Attr::REVERSE | Attr::STANDOUT)
+    { ::std::mem::swap(&mut fg, &mut bg); }
+    if self.attr.contains(Attr::INVIS)
+    { fg = bg; }
+
+    let mut css = Vec::new();
+    if fg != palette.fg
+    { css.push(format!("color: {}", hex(fg))); }
+    if bg != palette.bg
+    { css.push(format!("background-color: {}", hex(bg))); }
+    if self.attr.contains(Attr::BOLD)
+    { css.push("font-weight: bold".to_string()); }
+    if self.attr.contains(Attr::DIM)
+    { css.push("opacity: 0.5".to_string()); }
+    if self.attr.contains(Attr::ITALIC)
+    { css.push("font-style: italic".to_string()); }
+    if self.attr.contains(Attr::UNDERLINE)
+    { css.push("text-decoration: underline".to_string()); }
+    css.join("; ")
+  }
+}
+
+/* The cells of a row up to the last one that isn't a default blank. */
+fn trimmed(row: &[Cell]) -> &[Cell]
+{
+  let blank = Cell::default();
+  let end = row.iter().rposition(|c| *c != blank).map_or(0, |x| x + 1);
+  &row[..end]
+}
+
+
+fn sgr_color(color: Color, base: u32, bright: u32, extended: u32) -> Option<String>
+{
+  match color
+  {
+    Color::Default => None,
+    Color::Indexed(n) if n < 8 => Some((base + n as u32).to_string()),
+    Color::Indexed(n) if n < 16 => Some((bright + n as u32 - 8).to_string()),
+    Color::Indexed(n) => Some(format!("{};5;{}", extended, n)),
+    Color::Rgb(r, g, b) => Some(format!("{};2;{};{};{}", extended, r, g, b)),
+  }
+}
+
+
+fn hex((r, g, b): Rgb) -> String
+{ format!("#{:02x}{:02x}{:02x}", r, g, b) }
+
+
+fn escape_html(out: &mut String, text: &str)
+{
+  for c in text.chars()
+  {
+    match c
+    {
+      '&' => out.push_str("&amp;"),
+      '<' => out.push_str("&lt;"),
+      '>' => out.push_str("&gt;"),
+      '"' => out.push_str("&quot;"),
+      _ => out.push(c),
+    }
+  }
+}
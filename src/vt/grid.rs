@@ -0,0 +1,265 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: vt/grid.rs
+    Description:
+      The cells a terminal shows: text, video
+      attributes and colors, row by row, plus
+      golden file comparison for tests.
+*/
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use attr::Attr;
+
+/// Setting this variable makes `Grid::assert_golden` rewrite the golden
+/// files instead of comparing against them.
+pub const UPDATE_GOLDEN: &str = "NCURSES_UPDATE_GOLDEN";
+
+/// A foreground or background color as the terminal was told to show it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Color
+{
+  /// The terminal's own color, after `39m`/`49m` or a reset.
+  #[default]
+  Default,
+  /// One of the 256 palette entries; 0–7 are `COLOR_BLACK`..`COLOR_WHITE`.
+  Indexed(u8),
+  /// A direct color, from `38;2;r;g;b`.
+  Rgb(u8, u8, u8),
+}
+
+/// One character cell.
+///
+/// `attr` holds what the terminal was asked to render, so `A_STANDOUT`
+/// shows up as whatever the terminfo entry sends for it (`REVERSE` on
+/// xterm). The right half of a double-width character has an empty
+/// `text`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Cell
+{
+  /// The character and any combining marks after it.
+  pub text: String,
+  pub attr: Attr,
+  pub fg: Color,
+  pub bg: Color,
+}
+
+impl Cell
+{
+  /// A space on `bg`, which is what erasing leaves behind.
+  pub fn blank(bg: Color) -> Cell
+  { Cell { text: " ".to_string(), attr: Attr::NORMAL, fg: Color::Default, bg } }
+
+
+  /// Whether this is the right half of a double-width character.
+  pub fn is_continuation(&self) -> bool
+  { self.text.is_empty() }
+}
+
+impl Default for Cell
+{
+  fn default() -> Cell
+  { Cell::blank(Color::Default) }
+}
+
+/// The screen contents of a terminal, `lines` rows of `cols` cells.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid
+{
+  lines: usize,
+  cols: usize,
+  cells: Vec<Cell>,
+  cursor: (usize, usize),
+  cursor_visible: bool,
+}
+
+impl Grid
+{
+  /// A blank grid with the cursor at the top left.
+  pub fn new(lines: usize, cols: usize) -> Grid
+  {
+    Grid
+    {
+      lines,
+      cols,
+      cells: vec![Cell::default(); lines * cols],
+      cursor: (0, 0),
+      cursor_visible: true,
+    }
+  }
+
+
+  pub fn lines(&self) -> usize
+  { self.lines }
+
+
+  pub fn cols(&self) -> usize
+  { self.cols }
+
+
+  /// The cell at row `y`, column `x`. Panics when outside the grid.
+  pub fn cell(&self, y: usize, x: usize) -> &Cell
+  {
+    assert!(y < self.lines && x < self.cols, "cell ({}, {}) is outside a {}x{} grid", y, x, self.lines, self.cols);
+    &self.cells[y * self.cols + x]
+  }
+
+
+  /// Row `y` as a slice of cells. Panics when outside the grid.
+  pub fn row(&self, y: usize) -> &[Cell]
+  { &self.cells[y * self.cols..(y + 1) * self.cols] }
+
+
+  /// The text of row `y`, without trailing blanks.
+  pub fn row_text(&self, y: usize) -> String
+  {
+    let text: String = self.row(y).iter().map(|c| c.text.as_str()).collect();
+    text.trim_end_matches(' ').to_string()
+  }
+
+
+  /// Every row's text, each followed by a newline.
+  pub fn text(&self) -> String
+  {
+    let mut text = String::new();
+    for y in 0..self.lines
+    {
+      text.push_str(&self.row_text(y));
+      text.push('\n');
+    }
+    text
+  }
+
+
+  /// The cursor as (y, x).
+  pub fn cursor(&self) -> (usize, usize)
+  { self.cursor }
+
+
+  /// Whether the cursor was left visible (`curs_set`).
+  pub fn cursor_visible(&self) -> bool
+  { self.cursor_visible }
+
+
+  /// Where `needle` first starts, as (y, x), searching row by row.
+  /// Matches don't span rows.
+  pub fn find(&self, needle: &str) -> Option<(usize, usize)>
+  {
+    for y in 0..self.lines
+    {
+      let row = self.row(y);
+      for x in 0..self.cols
+      {
+        let mut rest = needle;
+        for cell in &row[x..]
+        {
+          if rest.is_empty()
+          { break; }
+          if !rest.starts_with(cell.text.as_str())
+          { break; }
+          rest = &rest[cell.text.len()..];
+        }
+        if rest.is_empty() && !row[x].is_continuation()
+        { return Some((y, x)); }
+      }
+    }
+    None
+  }
+
+
+  /// Compares `text()` with the file at `path`, panicking with both on
+  /// a difference. With `NCURSES_UPDATE_GOLDEN` set the file is written
+  /// instead, creating it and its directory as needed.
+  pub fn assert_golden<P: AsRef<Path>>(&self, path: P)
+  {
+    let path = path.as_ref();
+    let actual = self.text();
+    if env::var_os(UPDATE_GOLDEN).is_some()
+    {
+      write_golden(path, &actual)
+        .unwrap_or_else(|e| panic!("writing {}: {}", path.display(), e));
+      return;
+    }
+    let expected = match fs::read_to_string(path)
+    {
+      Ok(expected) => expected,
+      Err(e) => panic!("reading {}: {}; rerun with {}=1 to create it", path.display(), e, UPDATE_GOLDEN),
+    };
+    if expected != actual
+    {
+      panic!("screen differs from {}\n--- expected\n{}--- actual\n{}", path.display(), expected, actual);
+    }
+  }
+
+
+  pub(crate) fn set_cursor(&mut self, y: usize, x: usize)
+  { self.cursor = (y, x); }
+
+
+  pub(crate) fn set_cursor_visible(&mut self, visible: bool)
+  { self.cursor_visible = visible; }
+
+
+  pub(crate) fn cell_mut(&mut self, y: usize, x: usize) -> &mut Cell
+  { &mut self.cells[y * self.cols + x] }
+
+
+  pub(crate) fn row_mut(&mut self, y: usize) -> &mut [Cell]
+  { &mut self.cells[y * self.cols..(y + 1) * self.cols] }
+
+
+  /* Moves rows top..=bottom up by n (down when n < 0), blanking the
+     rows uncovered. */
+  pub(crate) fn scroll(&mut self, top: usize, bottom: usize, n: isize, bg: Color)
+  {
+    let height = bottom + 1 - top;
+    let count = n.unsigned_abs().min(height);
+    let region = &mut self.cells[top * self.cols..(bottom + 1) * self.cols];
+    if n > 0
+    {
+      region.rotate_left(count * self.cols);
+      for cell in &mut region[(height - count) * self.cols..]
+      { *cell = Cell::blank(bg); }
+    }
+    else
+    {
+      region.rotate_right(count * self.cols);
+      for cell in &mut region[..count * self.cols]
+      { *cell = Cell::blank(bg); }
+    }
+  }
+
+
+  /* A grid of the new size keeping what fits. */
+  pub(crate) fn resized(&self, lines: usize, cols: usize) -> Grid
+  {
+    let mut grid = Grid::new(lines, cols);
+    for y in 0..lines.min(self.lines)
+    {
+      for x in 0..cols.min(self.cols)
+      { *grid.cell_mut(y, x) = self.cell(y, x).clone(); }
+    }
+    grid.cursor = (self.cursor.0.min(lines - 1), self.cursor.1.min(cols - 1));
+    grid.cursor_visible = self.cursor_visible;
+    grid
+  }
+}
+
+impl fmt::Display for Grid
+{
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+  { f.write_str(&self.text()) }
+}
+
+fn write_golden(path: &Path, text: &str) -> io::Result<()>
+{
+  if let Some(dir) = path.parent()
+  { fs::create_dir_all(dir)?; }
+  fs::write(path, text)
+}
@@ -0,0 +1,339 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: vt/mod.rs
+    Description:
+      A headless terminal for tests: a screen
+      on a pseudo-terminal whose output is read
+      back into a grid of cells. Grids can also
+      be captured from windows and exported.
+*/
+
+use std::ffi::CStr;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::{AsRawFd, FromRawFd};
+use std::ptr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+
+use libc;
+use key::Key;
+use screen::Screen;
+use window::Window;
+
+pub use self::emulator::Emulator;
+pub use self::export::{Palette, Rgb};
+pub use self::grid::{Cell, Color, Grid, UPDATE_GOLDEN};
+
+mod capture;
+mod emulator;
+mod export;
+mod grid;
+
+/* Curses has one current screen and process-wide state such as
+   use_tioctl, so harnesses in parallel tests take turns. */
+static TURN: Mutex<()> = Mutex::new(());
+
+/* The master side of the pty and what has been read from it. */
+struct Output
+{
+  master: File,
+  emulator: Mutex<Emulator>,
+  done: AtomicBool,
+}
+
+impl Output
+{
+  /* Reads whatever is waiting, without blocking. */
+  fn drain(&self, emulator: &mut Emulator)
+  {
+    let mut buf = [0u8; 4096];
+    loop
+    {
+      match (&self.master).read(&mut buf)
+      {
+        Ok(0) => break,
+        Ok(n) => emulator.feed(&buf[..n]),
+        Err(ref e) if e.kind() == io::ErrorKind::Interrupted => (),
+        Err(_) => break,
+      }
+    }
+  }
+
+
+  /* Keeps the pty from filling up, which would block curses
+     mid-refresh, while the test is busy elsewhere. */
+  fn read_until_done(&self)
+  {
+    let mut fd = libc::pollfd { fd: self.master.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+    while !self.done.load(Ordering::SeqCst)
+    {
+      fd.revents = 0;
+      if unsafe { libc::poll(&mut fd, 1, 50) } <= 0
+      { continue; }
+      if fd.revents & libc::POLLIN != 0
+      {
+        let mut emulator = self.emulator.lock().unwrap_or_else(|e| e.into_inner());
+        self.drain(&mut emulator);
+      }
+      else if fd.revents & (libc::POLLHUP | libc::POLLERR | libc::POLLNVAL) != 0
+      { break; }
+    }
+  }
+}
+
+/// A curses screen on a pseudo-terminal of a fixed size, for tests.
+///
+/// The screen runs with `cbreak`, `noecho` and `keypad` on its
+/// `stdscr`. Input is scripted with `send` and `send_key`; what curses
+/// wrote is read back with `snapshot`, after a refresh. Only one harness
+/// exists at a time: creating another waits for the first to be dropped.
+/// Creating one calls `use_tioctl(true)`, so `$LINES` and `$COLUMNS`
+/// can't override the pty's size, and with `wide` sets `LC_CTYPE` to
+/// `C.UTF-8` so curses takes wide characters. Both stay in effect
+/// afterwards.
+///
+/// ```no_run
+/// # use ncurses::vt::Harness;
+/// # use ncurses::Key;
+/// let vt = Harness::new(5, 20).unwrap();
+/// let win = vt.stdscr();
+/// win.mvaddstr(1, 2, "hello");
+/// win.refresh();
+/// assert_eq!(vt.snapshot().row_text(1), "  hello");
+///
+/// vt.send_key(Key::Down).unwrap();
+/// assert_eq!(win.get_key(), Some(Key::Down));
+/// ```
+pub struct Harness
+{
+  screen: Screen,
+  slave: File,
+  output: Arc<Output>,
+  reader: Option<JoinHandle<()>>,
+  _turn: MutexGuard<'static, ()>,
+}
+
+impl Harness
+{
+  /// The terminal type used by `new`.
+  pub const TERM: &'static str = "xterm-256color";
+
+  /// A `lines` by `cols` screen of type `TERM`.
+  pub fn new(lines: u16, cols: u16) -> io::Result<Harness>
+  { Harness::with_term(Harness::TERM, lines, cols) }
+
+
+  /// A `lines` by `cols` screen of terminal type `term`, which must be
+  /// in the terminfo database. The grid only understands the xterm
+  /// family of control sequences.
+  pub fn with_term(term: &str, lines: u16, cols: u16) -> io::Result<Harness>
+  {
+    if lines == 0 || cols == 0
+    { return Err(io::Error::new(io::ErrorKind::InvalidInput, "a terminal needs at least one cell")); }
+    let turn = TURN.lock().unwrap_or_else(|e| e.into_inner());
+    let (master, slave) = open_pty(lines, cols)?;
+    ::use_tioctl(true);
+    #[cfg(feature="wide")]
+    ::setlocale(::LcCategory::ctype, "C.UTF-8");
+    let screen = Screen::new(Some(term), &slave, &slave)?;
+    screen.cbreak();
+    screen.noecho();
+    screen.stdscr().keypad(true);
+
+    let output = Arc::new(Output
+    {
+      master,
+      emulator: Mutex::new(Emulator::new(lines as usize, cols as usize)),
+      done: AtomicBool::new(false),
+    });
+    let reader =
+    {
+      let output = output.clone();
+      thread::Builder::new()
+        .name("ncurses-vt".to_string())
+        .spawn(move || output.read_until_done())?
+    };
+    Ok(Harness { screen, slave, output, reader: Some(reader), _turn: turn })
+  }
+
+
+  pub fn screen(&self) -> &Screen
+  { &self.screen }
+
+
+  pub fn stdscr(&self) -> Window<'_>
+  { self.screen.stdscr() }
+
+
+  /// Types `bytes` on the terminal's keyboard. While the pty's input
+  /// queue is full this waits for curses to read from it, giving up with
+  /// `TimedOut` after a second without progress; nothing reads it while
+  /// the test itself is busy sending.
+  pub fn send(&self, mut bytes: &[u8]) -> io::Result<()>
+  {
+    while !bytes.is_empty()
+    {
+      match (&self.output.master).write(bytes)
+      {
+        Ok(0) => return Err(io::Error::from(io::ErrorKind::WriteZero)),
+        Ok(n) => bytes = &bytes[n..],
+        Err(ref e) if e.kind() == io::ErrorKind::Interrupted => (),
+        Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => wait_writable(&self.output.master)?,
+        Err(e) => return Err(e),
+      }
+    }
+    Ok(())
+  }
+
+
+  pub fn send_str(&self, s: &str) -> io::Result<()>
+  { self.send(s.as_bytes()) }
+
+
+  /// Sends what the terminal would for `key`, as found in its terminfo
+  /// entry; an error if the entry has no string for it.
+  pub fn send_key(&self, key: Key) -> io::Result<()>
+  {
+    if let Key::Char(c) = key
+    {
+      let mut buf = [0u8; 4];
+      return self.send(c.encode_utf8(&mut buf).as_bytes());
+    }
+    match self.screen.keybound(key.code(), 0)
+    {
+      Some(s) => self.send(s.as_bytes()),
+      None => Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{} is not bound on this terminal", key))),
+    }
+  }
+
+
+  /// What the terminal shows, after everything curses has written so
+  /// far. Refresh before taking one.
+  pub fn snapshot(&self) -> Grid
+  {
+    let mut emulator = self.output.emulator.lock().unwrap_or_else(|e| e.into_inner());
+    self.output.drain(&mut emulator);
+    emulator.grid().clone()
+  }
+
+
+  /// Resizes the pty and tells curses with `resizeterm`, which queues a
+  /// `KEY_RESIZE`.
+  pub fn resize(&self, lines: u16, cols: u16) -> io::Result<()>
+  {
+    if lines == 0 || cols == 0
+    { return Err(io::Error::new(io::ErrorKind::InvalidInput, "a terminal needs at least one cell")); }
+    {
+      let mut emulator = self.output.emulator.lock().unwrap_or_else(|e| e.into_inner());
+      self.output.drain(&mut emulator);
+      set_size(&self.slave, lines, cols)?;
+      emulator.resize(lines as usize, cols as usize);
+    }
+    match self.screen.update_size()
+    {
+      Some(_) => Ok(()),
+      None => Err(io::Error::last_os_error()),
+    }
+  }
+}
+
+impl Drop for Harness
+{
+  fn drop(&mut self)
+  {
+    self.output.done.store(true, Ordering::SeqCst);
+    if let Some(reader) = self.reader.take()
+    { let _ = reader.join(); }
+  }
+}
+
+fn set_size<F: AsRawFd>(tty: &F, lines: u16, cols: u16) -> io::Result<()>
+{
+  let size = libc::winsize { ws_row: lines, ws_col: cols, ws_xpixel: 0, ws_ypixel: 0 };
+  if unsafe { libc::ioctl(tty.as_raw_fd(), libc::TIOCSWINSZ, &size) } < 0
+  { return Err(io::Error::last_os_error()); }
+  Ok(())
+}
+
+
+/* Waits up to a second for room to write to the non-blocking `fd`. */
+fn wait_writable<F: AsRawFd>(fd: &F) -> io::Result<()>
+{
+  let mut poll = libc::pollfd { fd: fd.as_raw_fd(), events: libc::POLLOUT, revents: 0 };
+  match unsafe { libc::poll(&mut poll, 1, 1000) }
+  {
+    0 => Err(io::Error::new(io::ErrorKind::TimedOut, "the terminal's input queue stayed full")),
+    n if n < 0 =>
+    {
+      let err = io::Error::last_os_error();
+      if err.kind() == io::ErrorKind::Interrupted
+      { Ok(()) }
+      else
+      { Err(err) }
+    }
+    _ => Ok(()),
+  }
+}
+
+
+/* A pty pair, master non-blocking, already sized. */
+fn open_pty(lines: u16, cols: u16) -> io::Result<(File, File)>
+{
+  unsafe
+  {
+    let fd = libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY | libc::O_NONBLOCK);
+    if fd < 0
+    { return Err(io::Error::last_os_error()); }
+    let master = File::from_raw_fd(fd);
+    if libc::grantpt(fd) != 0 || libc::unlockpt(fd) != 0
+    { return Err(io::Error::last_os_error()); }
+    let mut name = [0 as libc::c_char; 128];
+    let err = libc::ptsname_r(fd, name.as_mut_ptr(), name.len());
+    if err != 0
+    { return Err(io::Error::from_raw_os_error(err)); }
+    let path = CStr::from_ptr(name.as_ptr()).to_string_lossy().into_owned();
+    let slave = OpenOptions::new().read(true).write(true)
+      .custom_flags(libc::O_NOCTTY)
+      .open(path)?;
+    set_size(&master, lines, cols)?;
+    Ok((master, slave))
+  }
+}
+
+
+extern "C"
+{
+  fn wcwidth(c: libc::wchar_t) -> libc::c_int;
+}
+
+/* wcwidth answers by LC_CTYPE, and in the C locale it only knows ASCII,
+   so the width is looked up in a UTF-8 locale whatever the program's. */
+thread_local!(static UTF8: libc::locale_t = unsafe
+{
+  let loc = libc::newlocale(libc::LC_CTYPE_MASK, b"C.UTF-8\0".as_ptr() as *const libc::c_char, ptr::null_mut());
+  if loc.is_null()
+  { libc::newlocale(libc::LC_CTYPE_MASK, b"en_US.UTF-8\0".as_ptr() as *const libc::c_char, ptr::null_mut()) }
+  else
+  { loc }
+});
+
+/* Columns `c` takes, as curses spaces it: 0 for combining marks, 2 for
+   wide characters. Ones wcwidth can't print take a column. */
+fn char_width(c: char) -> usize
+{
+  let width = UTF8.with(|&loc| unsafe
+  {
+    if loc.is_null()
+    { return wcwidth(c as libc::wchar_t); }
+    let previous = libc::uselocale(loc);
+    let width = wcwidth(c as libc::wchar_t);
+    libc::uselocale(previous);
+    width
+  });
+  if width < 0 { 1 } else { width as usize }
+}
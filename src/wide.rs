@@ -0,0 +1,479 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: wide.rs
+    Description:
+      Safe wrappers for the wide-character
+      (cchar_t) functions of ncursesw.
+*/
+
+#![allow(clippy::too_many_arguments)]
+
+use std::mem;
+use std::ptr;
+
+use libc::wchar_t;
+use ll;
+use ll::CCHARW_MAX;
+use super::*;
+
+pub type cchar_t = ll::cchar_t;
+
+/* A NUL-terminated wide copy of a Rust string. */
+pub(crate) fn to_wide(s: &str) -> Vec<wchar_t>
+{
+  let mut buf: Vec<wchar_t> = s.chars().map(|c| c as wchar_t).collect();
+  buf.push(0);
+  buf
+}
+
+/* Decodes wide characters up to the first NUL. */
+fn from_wide(buf: &[wchar_t]) -> String
+{
+  buf.iter()
+     .take_while(|&&c| c != 0)
+     .filter_map(|&c| char::from_u32(c as u32))
+     .collect()
+}
+
+
+/* Runs a call which fills in a single cell. */
+fn read_wch<F>(f: F) -> Option<cchar_t>
+  where F: FnOnce(*mut cchar_t) -> i32
+{
+  let mut wch: cchar_t = unsafe { mem::zeroed() };
+  match f(&mut wch)
+  {
+    ERR => None,
+    _ => Some(wch),
+  }
+}
+
+/* Runs a call which fills in at most `n` cells followed by a null cell. */
+fn read_wchnstr<F>(n: i32, f: F) -> Option<Vec<cchar_t>>
+  where F: FnOnce(*mut cchar_t, i32) -> i32
+{
+  if n < 0
+  { return None; }
+
+  let mut buf: Vec<cchar_t> = vec![unsafe { mem::zeroed() }; n as usize + 1];
+  match f(buf.as_mut_ptr(), n)
+  {
+    ERR => None,
+    _ =>
+    {
+      let len = buf.iter().position(|c| c.chars[0] == 0).unwrap_or(n as usize);
+      buf.truncate(len);
+      Some(buf)
+    }
+  }
+}
+
+/* Runs a call which fills in at most `n` characters followed by a NUL. */
+fn read_wstr<T, F>(n: i32, f: F) -> Option<String>
+  where T: Copy + Default + Into<i64>, F: FnOnce(*mut T, i32) -> i32
+{
+  if n < 0
+  { return None; }
+
+  let mut buf: Vec<T> = vec![T::default(); n as usize + 1];
+  match f(buf.as_mut_ptr(), n)
+  {
+    ERR => None,
+    _ => Some(buf.iter()
+                 .map(|&c| c.into())
+                 .take_while(|&c| c != 0)
+                 .filter_map(|c| char::from_u32(c as u32))
+                 .collect()),
+  }
+}
+
+
+/// Builds a complex character from a spacing character followed by
+/// up to four combining characters, e.g. `"e\u{301}"`.
+pub fn setcchar(wch: &str, attrs: attr_t, pair: i16) -> Option<cchar_t>
+{
+  if wch.chars().count() > CCHARW_MAX
+  { return None; }
+
+  let buf = to_wide(wch);
+  unsafe
+  {
+    let mut wcval: cchar_t = mem::zeroed();
+    match ll::setcchar(&mut wcval, buf.as_ptr(), attrs, pair, ptr::null())
+    {
+      ERR => None,
+      _ => Some(wcval),
+    }
+  }
+}
+
+
+/// Splits a complex character into its characters, attributes and
+/// color pair.
+pub fn getcchar(wcval: &cchar_t) -> Option<(String, attr_t, i16)>
+{
+  let mut buf: [wchar_t; CCHARW_MAX + 1] = [0; CCHARW_MAX + 1];
+  let mut attrs: attr_t = 0;
+  let mut pair: i16 = 0;
+  unsafe
+  {
+    match ll::getcchar(wcval, buf.as_mut_ptr(), &mut attrs, &mut pair, ptr::null_mut())
+    {
+      ERR => None,
+      _ => Some((from_wide(&buf), attrs, pair)),
+    }
+  }
+}
+
+
+pub fn add_wch(wch: &cchar_t) -> i32
+{ unsafe { ll::add_wch(wch) } }
+
+
+pub fn wadd_wch(w: WINDOW, wch: &cchar_t) -> i32
+{ unsafe { ll::wadd_wch(w, wch) } }
+
+
+pub fn mvadd_wch(y: i32, x: i32, wch: &cchar_t) -> i32
+{ unsafe { ll::mvadd_wch(y, x, wch) } }
+
+
+pub fn mvwadd_wch(w: WINDOW, y: i32, x: i32, wch: &cchar_t) -> i32
+{ unsafe { ll::mvwadd_wch(w, y, x, wch) } }
+
+
+pub fn add_wchnstr(s: &[cchar_t]) -> i32
+{ unsafe { ll::add_wchnstr(s.as_ptr(), s.len() as i32) } }
+
+
+pub fn wadd_wchnstr(w: WINDOW, s: &[cchar_t]) -> i32
+{ unsafe { ll::wadd_wchnstr(w, s.as_ptr(), s.len() as i32) } }
+
+
+pub fn mvwadd_wchnstr(w: WINDOW, y: i32, x: i32, s: &[cchar_t]) -> i32
+{ unsafe { ll::mvwadd_wchnstr(w, y, x, s.as_ptr(), s.len() as i32) } }
+
+
+pub fn addwstr(s: &str) -> i32
+{ unsafe { ll::addwstr(to_wide(s).as_ptr()) } }
+
+
+pub fn addnwstr(s: &str, n: i32) -> i32
+{ unsafe { ll::addnwstr(to_wide(s).as_ptr(), n) } }
+
+
+pub fn waddwstr(w: WINDOW, s: &str) -> i32
+{ unsafe { ll::waddwstr(w, to_wide(s).as_ptr()) } }
+
+
+pub fn waddnwstr(w: WINDOW, s: &str, n: i32) -> i32
+{ unsafe { ll::waddnwstr(w, to_wide(s).as_ptr(), n) } }
+
+
+pub fn mvaddwstr(y: i32, x: i32, s: &str) -> i32
+{ unsafe { ll::mvaddwstr(y, x, to_wide(s).as_ptr()) } }
+
+
+pub fn mvwaddwstr(w: WINDOW, y: i32, x: i32, s: &str) -> i32
+{ unsafe { ll::mvwaddwstr(w, y, x, to_wide(s).as_ptr()) } }
+
+
+pub fn mvwaddnwstr(w: WINDOW, y: i32, x: i32, s: &str, n: i32) -> i32
+{ unsafe { ll::mvwaddnwstr(w, y, x, to_wide(s).as_ptr(), n) } }
+
+
+pub fn ins_wch(wch: &cchar_t) -> i32
+{ unsafe { ll::ins_wch(wch) } }
+
+
+pub fn wins_wch(w: WINDOW, wch: &cchar_t) -> i32
+{ unsafe { ll::wins_wch(w, wch) } }
+
+
+pub fn ins_wstr(s: &str) -> i32
+{ unsafe { ll::ins_wstr(to_wide(s).as_ptr()) } }
+
+
+pub fn wins_wstr(w: WINDOW, s: &str) -> i32
+{ unsafe { ll::wins_wstr(w, to_wide(s).as_ptr()) } }
+
+
+pub fn wins_nwstr(w: WINDOW, s: &str, n: i32) -> i32
+{ unsafe { ll::wins_nwstr(w, to_wide(s).as_ptr(), n) } }
+
+
+pub fn mvwins_wstr(w: WINDOW, y: i32, x: i32, s: &str) -> i32
+{ unsafe { ll::mvwins_wstr(w, y, x, to_wide(s).as_ptr()) } }
+
+
+pub fn bkgrnd(wch: &cchar_t) -> i32
+{ unsafe { ll::bkgrnd(wch) } }
+
+
+pub fn bkgrndset(wch: &cchar_t)
+{ unsafe { ll::bkgrndset(wch) } }
+
+
+pub fn wbkgrnd(w: WINDOW, wch: &cchar_t) -> i32
+{ unsafe { ll::wbkgrnd(w, wch) } }
+
+
+pub fn wbkgrndset(w: WINDOW, wch: &cchar_t)
+{ unsafe { ll::wbkgrndset(w, wch) } }
+
+
+pub fn wgetbkgrnd(w: WINDOW) -> Option<cchar_t>
+{ read_wch(|p| unsafe { ll::wgetbkgrnd(w, p) }) }
+
+
+pub fn border_set(ls: &cchar_t, rs: &cchar_t, ts: &cchar_t, bs: &cchar_t,
+                  tl: &cchar_t, tr: &cchar_t, bl: &cchar_t, br: &cchar_t) -> i32
+{ unsafe { ll::border_set(ls, rs, ts, bs, tl, tr, bl, br) } }
+
+
+pub fn wborder_set(w: WINDOW, ls: &cchar_t, rs: &cchar_t, ts: &cchar_t, bs: &cchar_t,
+                   tl: &cchar_t, tr: &cchar_t, bl: &cchar_t, br: &cchar_t) -> i32
+{ unsafe { ll::wborder_set(w, ls, rs, ts, bs, tl, tr, bl, br) } }
+
+
+pub fn box_set(w: WINDOW, verch: &cchar_t, horch: &cchar_t) -> i32
+{ unsafe { ll::box_set(w, verch, horch) } }
+
+
+pub fn hline_set(wch: &cchar_t, n: i32) -> i32
+{ unsafe { ll::hline_set(wch, n) } }
+
+
+pub fn whline_set(w: WINDOW, wch: &cchar_t, n: i32) -> i32
+{ unsafe { ll::whline_set(w, wch, n) } }
+
+
+pub fn mvhline_set(y: i32, x: i32, wch: &cchar_t, n: i32) -> i32
+{ unsafe { ll::mvhline_set(y, x, wch, n) } }
+
+
+pub fn mvwhline_set(w: WINDOW, y: i32, x: i32, wch: &cchar_t, n: i32) -> i32
+{ unsafe { ll::mvwhline_set(w, y, x, wch, n) } }
+
+
+pub fn vline_set(wch: &cchar_t, n: i32) -> i32
+{ unsafe { ll::vline_set(wch, n) } }
+
+
+pub fn wvline_set(w: WINDOW, wch: &cchar_t, n: i32) -> i32
+{ unsafe { ll::wvline_set(w, wch, n) } }
+
+
+pub fn mvvline_set(y: i32, x: i32, wch: &cchar_t, n: i32) -> i32
+{ unsafe { ll::mvvline_set(y, x, wch, n) } }
+
+
+pub fn mvwvline_set(w: WINDOW, y: i32, x: i32, wch: &cchar_t, n: i32) -> i32
+{ unsafe { ll::mvwvline_set(w, y, x, wch, n) } }
+
+
+pub fn echo_wchar(wch: &cchar_t) -> i32
+{ unsafe { ll::echo_wchar(wch) } }
+
+
+pub fn wecho_wchar(w: WINDOW, wch: &cchar_t) -> i32
+{ unsafe { ll::wecho_wchar(w, wch) } }
+
+
+pub fn pecho_wchar(pad: WINDOW, wch: &cchar_t) -> i32
+{ unsafe { ll::pecho_wchar(pad, wch) } }
+
+/*
+ * Reading back the screen.
+ */
+
+impl cchar_t
+{
+  /// The spacing character followed by any combining characters.
+  pub fn text(&self) -> String
+  { getcchar(self).map(|(s, _, _)| s).unwrap_or_default() }
+
+
+  pub fn attrs(&self) -> attr_t
+  { getcchar(self).map(|(_, a, _)| a).unwrap_or(0) }
+
+
+  pub fn pair(&self) -> i16
+  { getcchar(self).map(|(_, _, p)| p).unwrap_or(0) }
+}
+
+
+pub fn in_wch() -> Option<cchar_t>
+{ read_wch(|p| unsafe { ll::in_wch(p) }) }
+
+
+pub fn win_wch(w: WINDOW) -> Option<cchar_t>
+{ read_wch(|p| unsafe { ll::win_wch(w, p) }) }
+
+
+pub fn mvin_wch(y: i32, x: i32) -> Option<cchar_t>
+{ read_wch(|p| unsafe { ll::mvin_wch(y, x, p) }) }
+
+
+pub fn mvwin_wch(w: WINDOW, y: i32, x: i32) -> Option<cchar_t>
+{ read_wch(|p| unsafe { ll::mvwin_wch(w, y, x, p) }) }
+
+
+pub fn in_wchnstr(n: i32) -> Option<Vec<cchar_t>>
+{ read_wchnstr(n, |p, n| unsafe { ll::in_wchnstr(p, n) }) }
+
+
+pub fn win_wchnstr(w: WINDOW, n: i32) -> Option<Vec<cchar_t>>
+{ read_wchnstr(n, |p, n| unsafe { ll::win_wchnstr(w, p, n) }) }
+
+
+pub fn mvwin_wchnstr(w: WINDOW, y: i32, x: i32, n: i32) -> Option<Vec<cchar_t>>
+{ read_wchnstr(n, |p, n| unsafe { ll::mvwin_wchnstr(w, y, x, p, n) }) }
+
+
+pub fn innwstr(n: i32) -> Option<String>
+{ read_wstr(n, |p: *mut wchar_t, n| unsafe { ll::innwstr(p, n) }) }
+
+
+pub fn winnwstr(w: WINDOW, n: i32) -> Option<String>
+{ read_wstr(n, |p: *mut wchar_t, n| unsafe { ll::winnwstr(w, p, n) }) }
+
+
+pub fn mvwinnwstr(w: WINDOW, y: i32, x: i32, n: i32) -> Option<String>
+{ read_wstr(n, |p: *mut wchar_t, n| unsafe { ll::mvwinnwstr(w, y, x, p, n) }) }
+
+/*
+ * Line input, edited by ncurses itself.
+ */
+
+pub fn getn_wstr(n: i32) -> Option<String>
+{ read_wstr(n, |p: *mut winttype, n| unsafe { ll::getn_wstr(p, n) }) }
+
+
+pub fn wgetn_wstr(w: WINDOW, n: i32) -> Option<String>
+{ read_wstr(n, |p: *mut winttype, n| unsafe { ll::wgetn_wstr(w, p, n) }) }
+
+
+pub fn mvwgetn_wstr(w: WINDOW, y: i32, x: i32, n: i32) -> Option<String>
+{ read_wstr(n, |p: *mut winttype, n| unsafe { ll::mvwgetn_wstr(w, y, x, p, n) }) }
+
+/*
+ * Wide-character methods on owned windows.
+ */
+
+impl<'a> Window<'a>
+{
+  pub fn add_wch(&self, wch: &cchar_t) -> i32
+  { wadd_wch(self.as_raw(), wch) }
+
+
+  pub fn mvadd_wch(&self, y: i32, x: i32, wch: &cchar_t) -> i32
+  { mvwadd_wch(self.as_raw(), y, x, wch) }
+
+
+  pub fn add_wchnstr(&self, s: &[cchar_t]) -> i32
+  { wadd_wchnstr(self.as_raw(), s) }
+
+
+  pub fn addwstr(&self, s: &str) -> i32
+  { waddwstr(self.as_raw(), s) }
+
+
+  pub fn addnwstr(&self, s: &str, n: i32) -> i32
+  { waddnwstr(self.as_raw(), s, n) }
+
+
+  pub fn mvaddwstr(&self, y: i32, x: i32, s: &str) -> i32
+  { mvwaddwstr(self.as_raw(), y, x, s) }
+
+
+  pub fn ins_wch(&self, wch: &cchar_t) -> i32
+  { wins_wch(self.as_raw(), wch) }
+
+
+  pub fn ins_wstr(&self, s: &str) -> i32
+  { wins_wstr(self.as_raw(), s) }
+
+
+  pub fn mvins_wstr(&self, y: i32, x: i32, s: &str) -> i32
+  { mvwins_wstr(self.as_raw(), y, x, s) }
+
+
+  pub fn bkgrnd(&self, wch: &cchar_t) -> i32
+  { wbkgrnd(self.as_raw(), wch) }
+
+
+  pub fn bkgrndset(&self, wch: &cchar_t)
+  { wbkgrndset(self.as_raw(), wch) }
+
+
+  pub fn getbkgrnd(&self) -> Option<cchar_t>
+  { wgetbkgrnd(self.as_raw()) }
+
+
+  pub fn border_set(&self, ls: &cchar_t, rs: &cchar_t, ts: &cchar_t, bs: &cchar_t,
+                    tl: &cchar_t, tr: &cchar_t, bl: &cchar_t, br: &cchar_t) -> i32
+  { wborder_set(self.as_raw(), ls, rs, ts, bs, tl, tr, bl, br) }
+
+
+  pub fn box_set(&self, verch: &cchar_t, horch: &cchar_t) -> i32
+  { box_set(self.as_raw(), verch, horch) }
+
+
+  pub fn hline_set(&self, wch: &cchar_t, n: i32) -> i32
+  { whline_set(self.as_raw(), wch, n) }
+
+
+  pub fn mvhline_set(&self, y: i32, x: i32, wch: &cchar_t, n: i32) -> i32
+  { mvwhline_set(self.as_raw(), y, x, wch, n) }
+
+
+  pub fn vline_set(&self, wch: &cchar_t, n: i32) -> i32
+  { wvline_set(self.as_raw(), wch, n) }
+
+
+  pub fn mvvline_set(&self, y: i32, x: i32, wch: &cchar_t, n: i32) -> i32
+  { mvwvline_set(self.as_raw(), y, x, wch, n) }
+
+
+  pub fn echo_wchar(&self, wch: &cchar_t) -> i32
+  { wecho_wchar(self.as_raw(), wch) }
+
+
+  pub fn pecho_wchar(&self, wch: &cchar_t) -> i32
+  { pecho_wchar(self.as_raw(), wch) }
+
+
+  pub fn in_wch(&self) -> Option<cchar_t>
+  { win_wch(self.as_raw()) }
+
+
+  pub fn mvin_wch(&self, y: i32, x: i32) -> Option<cchar_t>
+  { mvwin_wch(self.as_raw(), y, x) }
+
+
+  pub fn in_wchnstr(&self, n: i32) -> Option<Vec<cchar_t>>
+  { win_wchnstr(self.as_raw(), n) }
+
+
+  pub fn mvin_wchnstr(&self, y: i32, x: i32, n: i32) -> Option<Vec<cchar_t>>
+  { mvwin_wchnstr(self.as_raw(), y, x, n) }
+
+
+  pub fn innwstr(&self, n: i32) -> Option<String>
+  { winnwstr(self.as_raw(), n) }
+
+
+  pub fn mvinnwstr(&self, y: i32, x: i32, n: i32) -> Option<String>
+  { mvwinnwstr(self.as_raw(), y, x, n) }
+
+
+  pub fn getn_wstr(&self, n: i32) -> Option<String>
+  { wgetn_wstr(self.as_raw(), n) }
+
+
+  pub fn mvgetn_wstr(&self, y: i32, x: i32, n: i32) -> Option<String>
+  { mvwgetn_wstr(self.as_raw(), y, x, n) }
+}
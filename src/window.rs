@@ -0,0 +1,570 @@
+/*
+    Copyright © 2013 Free Software Foundation, Inc
+    See licensing in LICENSE file
+
+    File: window.rs
+    Description:
+      An owned window handle which frees
+      itself with delwin when dropped.
+*/
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+
+use ll;
+use super::*;
+
+/// An owned curses window.
+///
+/// The underlying `WINDOW` is released with `delwin` when the value is
+/// dropped. Windows created with `derwin`, `subwin` or `subpad` borrow
+/// their parent, so a child can never outlive the window it shares
+/// memory with.
+pub struct Window<'a>
+{
+  handle: WINDOW,
+  owned: bool,
+  _parent: PhantomData<&'a Window<'a>>,
+}
+
+impl Window<'static>
+{
+  pub fn new(lines: i32, cols: i32, begin_y: i32, begin_x: i32) -> Option<Window<'static>>
+  { unsafe { Window::from_ptr(ll::newwin(lines, cols, begin_y, begin_x)) } }
+
+
+  pub fn new_pad(lines: i32, cols: i32) -> Option<Window<'static>>
+  { unsafe { Window::from_ptr(ll::newpad(lines, cols)) } }
+
+
+  /// A non-owning handle to `stdscr`, which is never passed to `delwin`.
+  pub fn stdscr() -> Window<'static>
+  { Window { handle: stdscr(), owned: false, _parent: PhantomData } }
+
+
+  /// Takes ownership of a raw window; it will be freed on drop.
+  ///
+  /// # Safety
+  ///
+  /// `w` must be a valid window which nothing else deletes.
+  pub unsafe fn from_raw(w: WINDOW) -> Window<'static>
+  { Window { handle: w, owned: true, _parent: PhantomData } }
+}
+
+impl<'a> Window<'a>
+{
+  pub(crate) fn from_ptr<'b>(w: WINDOW) -> Option<Window<'b>>
+  {
+    if w.is_null()
+    { None }
+    else
+    { Some(Window { handle: w, owned: true, _parent: PhantomData }) }
+  }
+
+
+  /* A handle to a window something else frees, such as a screen's stdscr. */
+  pub(crate) fn borrowed<'b>(w: WINDOW) -> Window<'b>
+  { Window { handle: w, owned: false, _parent: PhantomData } }
+
+
+  /// The raw handle, for use with the free functions and FFI.
+  pub fn as_raw(&self) -> WINDOW
+  { self.handle }
+
+
+  /// Releases ownership of the raw handle without calling `delwin`.
+  pub fn into_raw(self) -> WINDOW
+  {
+    let w = self.handle;
+    mem::forget(self);
+    w
+  }
+
+  /* Creation of related windows. */
+
+  pub fn derwin(&self, lines: i32, cols: i32, begin_y: i32, begin_x: i32) -> Option<Window<'_>>
+  { unsafe { Window::from_ptr(ll::derwin(self.handle, lines, cols, begin_y, begin_x)) } }
+
+
+  pub fn subwin(&self, lines: i32, cols: i32, begin_y: i32, begin_x: i32) -> Option<Window<'_>>
+  { unsafe { Window::from_ptr(ll::subwin(self.handle, lines, cols, begin_y, begin_x)) } }
+
+
+  pub fn subpad(&self, lines: i32, cols: i32, begin_y: i32, begin_x: i32) -> Option<Window<'_>>
+  { unsafe { Window::from_ptr(ll::subpad(self.handle, lines, cols, begin_y, begin_x)) } }
+
+
+  pub fn dupwin(&self) -> Option<Window<'static>>
+  { unsafe { Window::from_ptr(ll::dupwin(self.handle)) } }
+
+
+  pub fn parent(&self) -> Option<WINDOW>
+  {
+    let p = wgetparent(self.handle);
+    if p.is_null() { None } else { Some(p) }
+  }
+
+  /* Output. */
+
+  pub fn addch(&self, ch: chtype) -> i32
+  { waddch(self.handle, ch) }
+
+
+  pub fn mvaddch(&self, y: i32, x: i32, ch: chtype) -> i32
+  { mvwaddch(self.handle, y, x, ch) }
+
+
+  pub fn addchnstr(&self, s: &[chtype], n: i32) -> i32
+  { waddchnstr(self.handle, s, n) }
+
+
+  pub fn mvaddchnstr(&self, y: i32, x: i32, s: &[chtype], n: i32) -> i32
+  { mvwaddchnstr(self.handle, y, x, s, n) }
+
+
+  pub fn addchstr(&self, s: &[chtype]) -> i32
+  { waddchstr(self.handle, s) }
+
+
+  pub fn mvaddchstr(&self, y: i32, x: i32, s: &[chtype]) -> i32
+  { mvwaddchstr(self.handle, y, x, s) }
+
+
+  pub fn addnstr(&self, s: &str, n: i32) -> i32
+  { waddnstr(self.handle, s, n) }
+
+
+  pub fn mvaddnstr(&self, y: i32, x: i32, s: &str, n: i32) -> i32
+  { mvwaddnstr(self.handle, y, x, s, n) }
+
+
+  pub fn addstr(&self, s: &str) -> i32
+  { waddstr(self.handle, s) }
+
+
+  pub fn mvaddstr(&self, y: i32, x: i32, s: &str) -> i32
+  { mvwaddstr(self.handle, y, x, s) }
+
+
+  pub fn printw(&self, s: &str) -> i32
+  { wprintw(self.handle, s) }
+
+
+  pub fn mvprintw(&self, y: i32, x: i32, s: &str) -> i32
+  { mvwprintw(self.handle, y, x, s) }
+
+
+  pub fn echochar(&self, ch: chtype) -> i32
+  { wechochar(self.handle, ch) }
+
+
+  pub fn pechochar(&self, ch: chtype) -> i32
+  { pechochar(self.handle, ch) }
+
+
+  pub fn insch(&self, ch: chtype) -> i32
+  { winsch(self.handle, ch) }
+
+
+  pub fn mvinsch(&self, y: i32, x: i32, ch: chtype) -> i32
+  { mvwinsch(self.handle, y, x, ch) }
+
+
+  pub fn insstr(&self, s: &str) -> i32
+  { winsstr(self.handle, s) }
+
+
+  pub fn mvinsstr(&self, y: i32, x: i32, s: &str) -> i32
+  { mvwinsstr(self.handle, y, x, s) }
+
+
+  pub fn insnstr(&self, s: &str, n: i32) -> i32
+  { winsnstr(self.handle, s, n) }
+
+
+  pub fn mvinsnstr(&self, y: i32, x: i32, s: &str, n: i32) -> i32
+  { mvwinsnstr(self.handle, y, x, s, n) }
+
+
+  pub fn delch(&self) -> i32
+  { wdelch(self.handle) }
+
+
+  pub fn mvdelch(&self, y: i32, x: i32) -> i32
+  { mvwdelch(self.handle, y, x) }
+
+
+  pub fn deleteln(&self) -> i32
+  { wdeleteln(self.handle) }
+
+
+  pub fn insertln(&self) -> i32
+  { winsertln(self.handle) }
+
+
+  pub fn insdelln(&self, n: i32) -> i32
+  { winsdelln(self.handle, n) }
+
+  /* Attributes and colors. */
+
+  pub fn attron(&self, attr: NCURSES_ATTR_T) -> i32
+  { wattron(self.handle, attr) }
+
+
+  pub fn attroff(&self, attr: NCURSES_ATTR_T) -> i32
+  { wattroff(self.handle, attr) }
+
+
+  pub fn attrset(&self, attr: NCURSES_ATTR_T) -> i32
+  { wattrset(self.handle, attr) }
+
+
+  pub fn attr_get(&self, attrs: &mut attr_t, pair: &mut i16) -> i32
+  { wattr_get(self.handle, attrs, pair) }
+
+
+  pub fn attr_on(&self, attr: attr_t) -> i32
+  { wattr_on(self.handle, attr) }
+
+
+  pub fn attr_off(&self, attr: attr_t) -> i32
+  { wattr_off(self.handle, attr) }
+
+
+  pub fn attr_set(&self, attrs: attr_t, pair: i16) -> i32
+  { wattr_set(self.handle, attrs, pair) }
+
+
+  pub fn color_set(&self, pair: i16) -> i32
+  { wcolor_set(self.handle, pair) }
+
+
+  pub fn chgat(&self, n: i32, attr: attr_t, color: i16) -> i32
+  { wchgat(self.handle, n, attr, color) }
+
+
+  pub fn mvchgat(&self, y: i32, x: i32, n: i32, attr: attr_t, color: i16) -> i32
+  { mvwchgat(self.handle, y, x, n, attr, color) }
+
+
+  pub fn standout(&self) -> i32
+  { wstandout(self.handle) }
+
+
+  pub fn standend(&self) -> i32
+  { wstandend(self.handle) }
+
+
+  pub fn bkgd(&self, ch: chtype) -> i32
+  { wbkgd(self.handle, ch) }
+
+
+  pub fn bkgdset(&self, ch: chtype)
+  { wbkgdset(self.handle, ch) }
+
+
+  pub fn getbkgd(&self) -> chtype
+  { getbkgd(self.handle) }
+
+  /* Borders and lines. */
+
+  #[allow(clippy::too_many_arguments)]
+  pub fn border(&self, ls: chtype, rs: chtype, ts: chtype, bs: chtype, tl: chtype, tr: chtype, bl: chtype, br: chtype) -> i32
+  { wborder(self.handle, ls, rs, ts, bs, tl, tr, bl, br) }
+
+
+  pub fn box_(&self, v: chtype, h: chtype) -> i32
+  { box_(self.handle, v, h) }
+
+
+  pub fn hline(&self, ch: chtype, n: i32) -> i32
+  { whline(self.handle, ch, n) }
+
+
+  pub fn mvhline(&self, y: i32, x: i32, ch: chtype, n: i32) -> i32
+  { mvwhline(self.handle, y, x, ch, n) }
+
+
+  pub fn vline(&self, ch: chtype, n: i32) -> i32
+  { wvline(self.handle, ch, n) }
+
+
+  pub fn mvvline(&self, y: i32, x: i32, ch: chtype, n: i32) -> i32
+  { mvwvline(self.handle, y, x, ch, n) }
+
+  /* Clearing. */
+
+  pub fn clear(&self) -> i32
+  { wclear(self.handle) }
+
+
+  pub fn erase(&self) -> i32
+  { werase(self.handle) }
+
+
+  pub fn clrtobot(&self) -> i32
+  { wclrtobot(self.handle) }
+
+
+  pub fn clrtoeol(&self) -> i32
+  { wclrtoeol(self.handle) }
+
+  /* Cursor and geometry. */
+
+  pub fn mv(&self, y: i32, x: i32) -> i32
+  { wmove(self.handle, y, x) }
+
+
+  pub fn getcurx(&self) -> i32
+  { getcurx(self.handle) }
+
+
+  pub fn getcury(&self) -> i32
+  { getcury(self.handle) }
+
+
+  pub fn getyx(&self) -> (i32, i32)
+  { (getcury(self.handle), getcurx(self.handle)) }
+
+
+  pub fn getbegyx(&self) -> (i32, i32)
+  { (getbegy(self.handle), getbegx(self.handle)) }
+
+
+  pub fn getmaxyx(&self) -> (i32, i32)
+  { (getmaxy(self.handle), getmaxx(self.handle)) }
+
+
+  pub fn getparyx(&self) -> (i32, i32)
+  { (getpary(self.handle), getparx(self.handle)) }
+
+
+  pub fn mvwin(&self, y: i32, x: i32) -> i32
+  { mvwin(self.handle, y, x) }
+
+
+  pub fn mvderwin(&self, y: i32, x: i32) -> i32
+  { mvderwin(self.handle, y, x) }
+
+
+  pub fn resize(&self, lines: i32, cols: i32) -> i32
+  { wresize(self.handle, lines, cols) }
+
+
+  pub fn enclose(&self, y: i32, x: i32) -> bool
+  { wenclose(self.handle, y, x) }
+
+  /* Refresh and touch. */
+
+  pub fn refresh(&self) -> i32
+  { wrefresh(self.handle) }
+
+
+  pub fn noutrefresh(&self) -> i32
+  { wnoutrefresh(self.handle) }
+
+
+  pub fn prefresh(&self, pmin_row: i32, pmin_col: i32, smin_row: i32, smin_col: i32, smax_row: i32, smax_col: i32) -> i32
+  { prefresh(self.handle, pmin_row, pmin_col, smin_row, smin_col, smax_row, smax_col) }
+
+
+  pub fn pnoutrefresh(&self, pmin_row: i32, pmin_col: i32, smin_row: i32, smin_col: i32, smax_row: i32, smax_col: i32) -> i32
+  { pnoutrefresh(self.handle, pmin_row, pmin_col, smin_row, smin_col, smax_row, smax_col) }
+
+
+  pub fn redrawwin(&self) -> i32
+  { redrawwin(self.handle) }
+
+
+  pub fn redrawln(&self, start: i32, n: i32) -> i32
+  { wredrawln(self.handle, start, n) }
+
+
+  pub fn touchwin(&self) -> i32
+  { touchwin(self.handle) }
+
+
+  pub fn touchline(&self, start: i32, count: i32) -> i32
+  { touchline(self.handle, start, count) }
+
+
+  pub fn touchln(&self, y: i32, n: i32, changed: i32) -> i32
+  { wtouchln(self.handle, y, n, changed) }
+
+
+  pub fn untouchwin(&self) -> i32
+  { untouchwin(self.handle) }
+
+
+  pub fn is_wintouched(&self) -> bool
+  { is_wintouched(self.handle) }
+
+
+  pub fn is_linetouched(&self, line: i32) -> bool
+  { is_linetouched(self.handle, line) }
+
+
+  pub fn syncup(&self)
+  { wsyncup(self.handle) }
+
+
+  pub fn syncdown(&self)
+  { wsyncdown(self.handle) }
+
+
+  pub fn cursyncup(&self)
+  { wcursyncup(self.handle) }
+
+  /* Copying between windows. */
+
+  pub fn overlay(&self, dst: &Window) -> i32
+  { overlay(self.handle, dst.handle) }
+
+
+  pub fn overwrite(&self, dst: &Window) -> i32
+  { overwrite(self.handle, dst.handle) }
+
+
+  #[allow(clippy::too_many_arguments)]
+  pub fn copywin(&self, dst: &Window, src_min_row: i32, src_min_col: i32,
+                 dest_min_row: i32, dest_min_col: i32, dest_max_row: i32,
+                 dest_max_col: i32, overlay: bool) -> i32
+  {
+    copywin(self.handle, dst.handle, src_min_row, src_min_col, dest_min_row,
+            dest_min_col, dest_max_row, dest_max_col, overlay as i32)
+  }
+
+  /* Input. */
+
+  pub fn getch(&self) -> i32
+  { wgetch(self.handle) }
+
+
+  pub fn mvgetch(&self, y: i32, x: i32) -> i32
+  { mvwgetch(self.handle, y, x) }
+
+
+  pub fn get_wch(&self) -> Option<WchResult>
+  { wget_wch(self.handle) }
+
+
+  /// Reads a key, decoding multibyte characters when built with `wide`.
+  /// Resizes are reported as `Key::Resize`; see `install_resize_handler`.
+  #[cfg(feature="wide")]
+  pub fn get_key(&self) -> Option<Key>
+  { resize::read_key(|| wget_wch(self.handle).map(Key::from)) }
+
+
+  /// Reads a key; bytes above 0x7f are taken as Latin-1.
+  #[cfg(not(feature="wide"))]
+  pub fn get_key(&self) -> Option<Key>
+  { resize::read_key(|| Key::from_getch(wgetch(self.handle))) }
+
+
+  /// Reads a line of at most `n` characters; see `wgetnstr`.
+  pub fn getnstr(&self, n: i32) -> CursesResult<String>
+  { wgetnstr(self.handle, n) }
+
+
+  pub fn getstr(&self) -> CursesResult<String>
+  { wgetstr(self.handle) }
+
+
+  pub fn mvget_wch(&self, y: i32, x: i32) -> Option<WchResult>
+  { mvwget_wch(self.handle, y, x) }
+
+
+  pub fn inch(&self) -> chtype
+  { winch(self.handle) }
+
+
+  pub fn mvinch(&self, y: i32, x: i32) -> chtype
+  { mvwinch(self.handle, y, x) }
+
+  /* Options. */
+
+  pub fn keypad(&self, bf: bool) -> i32
+  { keypad(self.handle, bf) }
+
+
+  pub fn nodelay(&self, bf: bool) -> i32
+  { nodelay(self.handle, bf) }
+
+
+  pub fn notimeout(&self, bf: bool) -> i32
+  { notimeout(self.handle, bf) }
+
+
+  pub fn timeout(&self, delay: i32)
+  { wtimeout(self.handle, delay) }
+
+
+  pub fn clearok(&self, bf: bool) -> i32
+  { clearok(self.handle, bf) }
+
+
+  pub fn idlok(&self, bf: bool) -> i32
+  { idlok(self.handle, bf) }
+
+
+  pub fn idcok(&self, bf: bool)
+  { idcok(self.handle, bf) }
+
+
+  pub fn immedok(&self, bf: bool)
+  { immedok(self.handle, bf) }
+
+
+  pub fn leaveok(&self, bf: bool) -> i32
+  { leaveok(self.handle, bf) }
+
+
+  pub fn scrollok(&self, bf: bool) -> i32
+  { scrollok(self.handle, bf) }
+
+
+  pub fn syncok(&self, bf: bool) -> i32
+  { syncok(self.handle, bf) }
+
+
+  pub fn setscrreg(&self, top: i32, bot: i32) -> i32
+  { wsetscrreg(self.handle, top, bot) }
+
+
+  pub fn getscrreg(&self) -> (i32, i32)
+  {
+    let (mut top, mut bot) = (0, 0);
+    wgetscrreg(self.handle, &mut top, &mut bot);
+    (top, bot)
+  }
+
+
+  pub fn scroll(&self) -> i32
+  { scroll(self.handle) }
+
+
+  pub fn scrl(&self, n: i32) -> i32
+  { wscrl(self.handle, n) }
+}
+
+/// Lets `write!` print to the window; text is added literally at the cursor.
+impl<'a> fmt::Write for Window<'a>
+{
+  fn write_str(&mut self, s: &str) -> fmt::Result
+  {
+    if waddstr(self.handle, s) == ERR
+    { Err(fmt::Error) }
+    else
+    { Ok(()) }
+  }
+}
+
+impl<'a> Drop for Window<'a>
+{
+  fn drop(&mut self)
+  {
+    if self.owned && !self.handle.is_null()
+    { unsafe { ll::delwin(self.handle); } }
+  }
+}
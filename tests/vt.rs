@@ -0,0 +1,262 @@
+extern crate ncurses;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use ncurses::*;
+use ncurses::vt::{Color, Emulator, Grid, Harness, Palette};
+
+fn golden(name: &str) -> String
+{ format!("{}/tests/golden/{}", env!("CARGO_MANIFEST_DIR"), name) }
+
+
+#[test]
+fn text_lands_where_it_was_drawn()
+{
+  let vt = Harness::new(6, 30).unwrap();
+  assert_eq!(vt.screen().size(), (6, 30));
+  let win = vt.stdscr();
+  win.mvaddstr(2, 4, "hello");
+  win.mvaddstr(5, 25, "end");
+  win.refresh();
+
+  let grid = vt.snapshot();
+  assert_eq!((grid.lines(), grid.cols()), (6, 30));
+  assert_eq!(grid.row_text(2), "    hello");
+  assert_eq!(grid.find("end"), Some((5, 25)));
+  assert_eq!(grid.cursor(), (5, 28));
+}
+
+
+#[test]
+fn attributes_and_colors_are_recorded()
+{
+  let vt = Harness::new(4, 20).unwrap();
+  vt.screen().start_color();
+  vt.screen().init_pair(1, COLOR_RED, COLOR_BLUE);
+  let win = vt.stdscr();
+  win.attron(A_BOLD() | COLOR_PAIR(1));
+  win.mvaddstr(0, 0, "warn");
+  win.attrset(A_UNDERLINE());
+  win.addstr("ed");
+  win.refresh();
+
+  let grid = vt.snapshot();
+  let warn = grid.cell(0, 0);
+  assert_eq!(warn.text, "w");
+  assert!(warn.attr.contains(Attr::BOLD));
+  assert_eq!((warn.fg, warn.bg), (Color::Indexed(COLOR_RED as u8), Color::Indexed(COLOR_BLUE as u8)));
+  let ed = grid.cell(0, 4);
+  assert_eq!(ed.attr, Attr::UNDERLINE);
+  /* Without use_default_colors, pair 0 is white on black. */
+  assert_eq!((ed.fg, ed.bg), (Color::Indexed(COLOR_WHITE as u8), Color::Indexed(COLOR_BLACK as u8)));
+}
+
+
+#[cfg(all(feature="wide", feature="ext_colors"))]
+#[test]
+fn captured_extended_pairs_keep_their_colors()
+{
+  let vt = Harness::new(2, 10).unwrap();
+  vt.screen().start_color();
+  let pair = COLOR_PAIRS() - 1;
+  assert!(pair > i32::from(i16::MAX));
+  init_extended_pair(pair, i32::from(COLOR_RED), 200);
+  let win = vt.stdscr();
+  win.extended_color_set(pair);
+  win.mvaddstr(0, 0, "x");
+  win.refresh();
+
+  let captured = Grid::from_window(win.as_raw());
+  let shown = vt.snapshot();
+  assert_eq!((captured.cell(0, 0).fg, captured.cell(0, 0).bg), (Color::Indexed(COLOR_RED as u8), Color::Indexed(200)));
+  assert_eq!((shown.cell(0, 0).fg, shown.cell(0, 0).bg), (Color::Indexed(COLOR_RED as u8), Color::Indexed(200)));
+}
+
+
+#[test]
+fn boxed_window_matches_golden()
+{
+  let vt = Harness::new(6, 24).unwrap();
+  vt.stdscr().refresh();
+  let win = vt.screen().newwin(4, 16, 1, 2).unwrap();
+  win.box_(0, 0);
+  win.mvaddstr(0, 2, " title ");
+  win.mvaddstr(1, 1, "inside");
+  win.refresh();
+
+  let grid = vt.snapshot();
+  assert_eq!(grid.cell(1, 2).text, "┌");
+  grid.assert_golden(golden("box.txt"));
+}
+
+
+#[test]
+fn scripted_input_reaches_getch()
+{
+  let vt = Harness::new(4, 20).unwrap();
+  let win = vt.stdscr();
+  vt.send_str("hi").unwrap();
+  vt.send_key(Key::Up).unwrap();
+  vt.send_key(Key::F(2)).unwrap();
+
+  assert_eq!(win.getch(), 'h' as i32);
+  assert_eq!(win.getch(), 'i' as i32);
+  assert_eq!(win.getch(), KEY_UP);
+  assert_eq!(win.get_key(), Some(Key::F(2)));
+}
+
+
+#[test]
+fn sending_into_a_full_input_queue_times_out()
+{
+  let vt = Harness::new(2, 10).unwrap();
+  let err = vt.send(&vec![b'x'; 1 << 20]).unwrap_err();
+  assert_eq!(err.kind(), ::std::io::ErrorKind::TimedOut);
+  assert_eq!(vt.stdscr().getch(), 'x' as i32);
+}
+
+
+#[test]
+fn resize_changes_both_sides()
+{
+  let vt = Harness::new(4, 20).unwrap();
+  vt.resize(8, 30).unwrap();
+  assert_eq!(vt.screen().size(), (8, 30));
+  assert_eq!(vt.stdscr().getch(), KEY_RESIZE);
+
+  vt.stdscr().mvaddstr(7, 0, "bottom");
+  vt.stdscr().refresh();
+  let grid = vt.snapshot();
+  assert_eq!((grid.lines(), grid.cols()), (8, 30));
+  assert_eq!(grid.row_text(7), "bottom");
+}
+
+
+#[test]
+fn resize_callbacks_may_register_more()
+{
+  let vt = Harness::new(4, 20).unwrap();
+  let (outer, inner) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+  {
+    let (outer, inner) = (outer.clone(), inner.clone());
+    on_resize(move |_, _|
+    {
+      outer.set(outer.get() + 1);
+      let inner = inner.clone();
+      on_resize(move |_, _| inner.set(inner.get() + 1));
+    });
+  }
+
+  vt.resize(6, 20).unwrap();
+  assert_eq!(vt.stdscr().get_key(), Some(Key::Resize));
+  assert_eq!((outer.get(), inner.get()), (1, 0));
+
+  vt.resize(8, 20).unwrap();
+  assert_eq!(vt.stdscr().get_key(), Some(Key::Resize));
+  assert_eq!((outer.get(), inner.get()), (2, 1));
+}
+
+
+#[test]
+fn emulator_wraps_scrolls_and_draws_lines()
+{
+  let mut vt = Emulator::new(3, 5);
+  vt.feed(b"abcdefg\r\nxy\r\nz");
+  assert_eq!(vt.grid().text(), "fg\nxy\nz\n");
+
+  vt.feed(b"\x1b[H\x1b[2J\x1b(0lqk\x1b(B\x1b[38;5;196;48;2;1;2;3mA");
+  assert_eq!(vt.grid().row_text(0), "┌─┐A");
+  let a = vt.grid().cell(0, 3);
+  assert_eq!((a.fg, a.bg), (Color::Indexed(196), Color::Rgb(1, 2, 3)));
+
+  vt.feed("\x1b[2;1H漢字é".as_bytes());
+  assert_eq!(vt.grid().row_text(1), "漢字é");
+  assert!(vt.grid().cell(1, 1).is_continuation());
+  assert_eq!(vt.grid().cursor(), (1, 4));
+
+  /* Widths come from wcwidth, as for curses. */
+  vt.feed("\x1b[3;1H\u{1f680}e\u{301}".as_bytes());
+  assert_eq!(vt.grid().row_text(2), "\u{1f680}e\u{301}");
+  assert_eq!(vt.grid().cursor(), (2, 3));
+}
+
+
+#[test]
+fn captured_window_matches_the_terminal()
+{
+  let vt = Harness::new(6, 24).unwrap();
+  vt.screen().start_color();
+  vt.screen().init_pair(2, COLOR_GREEN, COLOR_BLACK);
+  let win = vt.stdscr();
+  win.box_(0, 0);
+  win.attron(COLOR_PAIR(2) | A_REVERSE());
+  win.mvaddstr(2, 2, "ok <go>");
+  win.attrset(A_NORMAL());
+  win.mvaddstr(3, 2, "x");
+  win.refresh();
+
+  let shown = vt.snapshot();
+  let captured = Grid::from_window(curscr());
+  assert_eq!(captured.text(), shown.text());
+  assert_eq!(captured.cell(2, 2), shown.cell(2, 2));
+  assert_eq!(captured.cell(0, 0).text, "┌");
+  assert_eq!(Grid::from_window(win.as_raw()).cursor(), (3, 3));
+  assert_eq!(win.getyx(), (3, 3));
+}
+
+
+#[test]
+fn grids_render_as_ansi_and_html()
+{
+  let mut vt = Emulator::new(2, 12);
+  vt.feed(b"a\x1b[1;31mb<\x1b[0m \x1b[7;38;5;200mc\x1b[0m\r\n\x1b[48;2;1;2;3m\x1b[K");
+  assert_eq!(vt.grid().text(), "ab< c\n\n");
+  assert_eq!(vt.grid().to_ansi(),
+             "a\x1b[0;1;31mb<\x1b[0m \x1b[0;7;38;5;200mc\x1b[0m\n\x1b[0;48;2;1;2;3m            \x1b[0m\n");
+  assert_eq!(vt.grid().to_html(&Palette::xterm()),
+             "<pre style=\"font-family: monospace; color: #e5e5e5; background-color: #000000\">\
+              a<span style=\"color: #cd0000; font-weight: bold\">b&lt;</span> \
+              <span style=\"color: #000000; background-color: #ff00d7\">c</span>\n\
+              <span style=\"background-color: #010203\">            </span></pre>\n");
+}
+
+
+#[cfg(feature="wide")]
+#[test]
+fn captured_wide_characters_take_two_cells()
+{
+  let vt = Harness::new(3, 12).unwrap();
+  let win = vt.stdscr();
+  win.mvaddstr(1, 1, "漢字 é");
+  win.refresh();
+
+  let captured = Grid::from_window(win.as_raw());
+  assert_eq!(captured.row_text(1), " 漢字 é");
+  assert!(captured.cell(1, 2).is_continuation());
+  assert_eq!(captured.text(), vt.snapshot().text());
+}
+
+
+#[test]
+fn getstr_edits_and_echoes_in_cbreak_mode()
+{
+  let vt = Harness::new(4, 30).unwrap();
+  vt.screen().echo();
+  vt.screen().nocbreak();
+  let win = vt.stdscr();
+  vt.send_str("ab\tx\x7fc").unwrap();
+  vt.send_key(Key::Left).unwrap();
+  vt.send_str("\n").unwrap();
+
+  assert_eq!(win.getstr().unwrap(), "ab\tc");
+  win.refresh();
+  assert_eq!(vt.snapshot().row_text(0), "ab      c");
+
+  /* Echo is back on afterwards, so getch shows what it reads. */
+  vt.screen().cbreak();
+  vt.send_str("z").unwrap();
+  assert_eq!(win.getch(), 'z' as i32);
+  win.refresh();
+  assert_eq!(vt.snapshot().row_text(0), "ab      cz");
+}